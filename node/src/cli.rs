@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::str::FromStr;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,6 +9,52 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Seal blocks on demand instead of running BABE: `instant`, `manual` or `interval=<ms>`.
+	#[structopt(long)]
+	pub sealing: Option<Sealing>,
+
+	#[structopt(flatten)]
+	pub eth: EthConfiguration,
+}
+
+/// Options for the `eth_*` RPCs.
+#[derive(Debug, Copy, Clone, StructOpt)]
+pub struct EthConfiguration {
+	/// Let `eth_sign` and `eth_sendTransaction` sign with the well-known development accounts.
+	#[structopt(long)]
+	pub enable_dev_signer: bool,
+
+	/// The most logs a single `eth_getLogs` call returns.
+	#[structopt(long, default_value = "10000")]
+	pub max_past_logs: u32,
+}
+
+/// How blocks are sealed in development mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when asked to through `engine_createBlock`.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			_ => match s.strip_prefix("interval=").map(str::parse) {
+				Some(Ok(0)) => Err("the sealing interval must be at least 1 ms".into()),
+				Some(Ok(millis)) => Ok(Sealing::Interval(millis)),
+				_ => Err(format!("unknown sealing method: {}", s)),
+			},
+		}
+	}
 }
 
 #[derive(Debug, StructOpt)]
@@ -38,4 +85,12 @@ pub enum Subcommand {
 	/// The custom benchmark subcommand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Try some command against runtime state.
+	#[cfg(feature = "try-runtime")]
+	TryRuntime(try_runtime_cli::TryRuntimeCmd),
+
+	/// Try some command against runtime state. Note: `try-runtime` feature must be enabled.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
 }
@@ -1,16 +1,47 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
-use sc_client_api::ExecutorProvider;
-use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use crate::cli::{EthConfiguration, Sealing};
+use fc_consensus::FrontierBlockImport;
+use fc_mapping_sync::MappingSyncWorker;
+use fc_rpc::EthTask;
+use fc_rpc_core::types::FilterPool;
+use futures::{
+	channel::mpsc,
+	future::{ready, Either, Ready},
+	stream::BoxStream,
+	Future, FutureExt, StreamExt, TryFutureExt,
+};
+use node_template_runtime::{
+	self, opaque::Block, DisabledAuthoritiesApi, Hash, RuntimeApi, MILLISECS_PER_BLOCK,
+};
+use sc_consensus_manual_seal::{
+	consensus::babe::{BabeConsensusDataProvider, SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
+use sc_cli::SubstrateCli;
+use sc_client_api::{BlockchainEvents, ExecutorProvider};
+use sc_consensus_babe::SlotProportion;
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_service::{error::Error as ServiceError, BasePath, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_consensus::SlotData;
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use sp_api::ProvideRuntimeApi;
+use sp_consensus::{Environment, Proposal, Proposer, SlotData};
+use sp_consensus_babe::digests::CompatibleDigestItem;
+use sp_inherents::InherentData;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+	Digest,
+};
+use std::{
+	collections::BTreeMap,
+	path::PathBuf,
+	pin::Pin,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
 
 // Our native executor instance.
 pub struct ExecutorDispatch;
@@ -36,6 +67,34 @@ type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type FullGrandpaBlockImport =
+	sc_finality_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+type FullFrontierBlockImport = FrontierBlockImport<Block, FullGrandpaBlockImport, FullClient>;
+
+/// How many blocks an `eth_newFilter` filter outlives its last poll by.
+const FILTER_RETAIN_THRESHOLD: u64 = 100;
+
+/// Where the Frontier database, which maps Ethereum hashes to Substrate ones, is kept.
+pub fn frontier_database_dir(config: &Configuration) -> PathBuf {
+	let config_dir = config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()))
+		.unwrap_or_else(|| {
+			BasePath::from_project("", "", &crate::cli::Cli::executable_name())
+				.config_dir(config.chain_spec.id())
+		});
+	config_dir.join("frontier").join("db")
+}
+
+pub fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backend<Block>>, String> {
+	Ok(Arc::new(fc_db::Backend::<Block>::new(&fc_db::DatabaseSettings {
+		source: fc_db::DatabaseSettingsSrc::RocksDb {
+			path: frontier_database_dir(config),
+			cache_size: 0,
+		},
+	})?))
+}
 
 pub fn new_partial(
 	config: &Configuration,
@@ -47,13 +106,10 @@ pub fn new_partial(
 		sc_consensus::DefaultImportQueue<Block, FullClient>,
 		sc_transaction_pool::FullPool<Block, FullClient>,
 		(
-			sc_finality_grandpa::GrandpaBlockImport<
-				FullBackend,
-				Block,
-				FullClient,
-				FullSelectChain,
-			>,
+			sc_consensus_babe::BabeBlockImport<Block, FullClient, FullFrontierBlockImport>,
 			sc_finality_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+			sc_consensus_babe::BabeLink<Block>,
+			Arc<fc_db::Backend<Block>>,
 			Option<Telemetry>,
 		),
 	>,
@@ -110,32 +166,42 @@ pub fn new_partial(
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let slot_duration = sc_consensus_aura::slot_duration(&*client)?.slot_duration();
+	let justification_import = grandpa_block_import.clone();
 
-	let import_queue =
-		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
-			block_import: grandpa_block_import.clone(),
-			justification_import: Some(Box::new(grandpa_block_import.clone())),
-			client: client.clone(),
-			create_inherent_data_providers: move |_, ()| async move {
-				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+	let frontier_backend = open_frontier_backend(config)?;
+	let frontier_block_import =
+		FrontierBlockImport::new(grandpa_block_import, client.clone(), frontier_backend.clone());
 
-				let slot =
-					sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
-						*timestamp,
-						slot_duration,
-					);
+	let (block_import, babe_link) = sc_consensus_babe::block_import(
+		sc_consensus_babe::Config::get(&*client)?,
+		frontier_block_import,
+		client.clone(),
+	)?;
 
-				Ok((timestamp, slot))
-			},
-			spawner: &task_manager.spawn_essential_handle(),
-			can_author_with: sp_consensus::CanAuthorWithNativeVersion::new(
-				client.executor().clone(),
-			),
-			registry: config.prometheus_registry(),
-			check_for_equivocation: Default::default(),
-			telemetry: telemetry.as_ref().map(|x| x.handle()),
-		})?;
+	let slot_duration = babe_link.config().slot_duration();
+
+	let import_queue = sc_consensus_babe::import_queue(
+		babe_link.clone(),
+		block_import.clone(),
+		Some(Box::new(justification_import)),
+		client.clone(),
+		select_chain.clone(),
+		move |_, ()| async move {
+			let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+			let slot =
+				sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
+					*timestamp,
+					slot_duration,
+				);
+
+			Ok((timestamp, slot))
+		},
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+		sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone()),
+		telemetry.as_ref().map(|x| x.handle()),
+	)?;
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -145,7 +211,7 @@ pub fn new_partial(
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (grandpa_block_import, grandpa_link, telemetry),
+		other: (block_import, grandpa_link, babe_link, frontier_backend, telemetry),
 	})
 }
 
@@ -156,8 +222,136 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 	Err("Remote Keystore not supported.")
 }
 
+/// Wraps the proposer factory BABE authors with, so that a disabled validator stays quiet until
+/// the next era instead of authoring blocks every node rejects.
+///
+/// BABE claims slots without regard to disabled authorities. The pre-digest of a claimed slot
+/// carries the author's index in the epoch BABE claimed the slot in, and `pallet_babe` checks that
+/// index against the disabled validators at the parent block. Proposing checks exactly the same.
+struct ProposeWhileEnabled<E> {
+	client: Arc<FullClient>,
+	inner: E,
+}
+
+/// A proposer of [`ProposeWhileEnabled`], building on `parent`.
+struct EnabledProposer<P> {
+	client: Arc<FullClient>,
+	parent: BlockId<Block>,
+	inner: P,
+}
+
+impl<E> Environment<Block> for ProposeWhileEnabled<E>
+where
+	E: Environment<Block>,
+	E::Proposer: Send,
+	E::Error: Send,
+{
+	type Proposer = EnabledProposer<E::Proposer>;
+	type CreateProposer =
+		Pin<Box<dyn Future<Output = Result<Self::Proposer, Self::Error>> + Send + 'static>>;
+	type Error = E::Error;
+
+	fn init(&mut self, parent_header: &<Block as BlockT>::Header) -> Self::CreateProposer {
+		let client = self.client.clone();
+		let parent = BlockId::Hash(parent_header.hash());
+		self.inner
+			.init(parent_header)
+			.map_ok(move |inner| EnabledProposer { client, parent, inner })
+			.boxed()
+	}
+}
+
+impl<P> Proposer<Block> for EnabledProposer<P>
+where
+	P: Proposer<Block>,
+	P::Error: Send,
+{
+	type Error = P::Error;
+	type Transaction = P::Transaction;
+	type Proposal = Either<
+		P::Proposal,
+		Ready<Result<Proposal<Block, Self::Transaction, Self::Proof>, Self::Error>>,
+	>;
+	type ProofRecording = P::ProofRecording;
+	type Proof = P::Proof;
+
+	fn propose(
+		self,
+		inherent_data: InherentData,
+		inherent_digests: Digest,
+		max_duration: Duration,
+		block_size_limit: Option<usize>,
+	) -> Self::Proposal {
+		let author = inherent_digests
+			.logs
+			.iter()
+			.find_map(|log| log.as_babe_pre_digest())
+			.map(|pre_digest| pre_digest.authority_index());
+		let disabled = match author {
+			Some(author) => self
+				.client
+				.runtime_api()
+				.disabled_authorities(&self.parent)
+				.map(|disabled| disabled.contains(&author))
+				.unwrap_or(false),
+			None => false,
+		};
+		if disabled {
+			return Either::Right(ready(Err(sp_consensus::Error::CannotPropose.into())))
+		}
+
+		Either::Left(self.inner.propose(
+			inherent_data,
+			inherent_digests,
+			max_duration,
+			block_size_limit,
+		))
+	}
+}
+
+/// The commands driving manual seal: those sent through the `engine_*` RPCs, plus a block for every
+/// transaction entering the pool or every interval, depending on `sealing`.
+fn sealing_commands(
+	sealing: Sealing,
+	rpc_commands: mpsc::Receiver<EngineCommand<Hash>>,
+	transaction_pool: &sc_transaction_pool::FullPool<Block, FullClient>,
+) -> BoxStream<'static, EngineCommand<Hash>> {
+	let seal_new_block = |create_empty| EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: false,
+		parent_hash: None,
+		sender: None,
+	};
+
+	match sealing {
+		Sealing::Manual => rpc_commands.boxed(),
+		Sealing::Instant => {
+			let imported = transaction_pool
+				.pool()
+				.validated_pool()
+				.import_notification_stream()
+				.map(move |_| seal_new_block(false));
+			futures::stream::select(rpc_commands, imported).boxed()
+		},
+		Sealing::Interval(millis) => {
+			let ticks = futures::stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(true), ()))
+			});
+			futures::stream::select(rpc_commands, ticks).boxed()
+		},
+	}
+}
+
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `sealing`, blocks are sealed on demand through manual seal instead of by BABE, and
+/// finalized through `engine_finalizeBlock` instead of by GRANDPA.
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+	eth_config: EthConfiguration,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -166,7 +360,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		mut keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (block_import, grandpa_link, mut telemetry),
+		other: (block_import, grandpa_link, babe_link, frontier_backend, mut telemetry),
 	} = new_partial(&config)?;
 
 	if let Some(url) = &config.keystore_remote {
@@ -211,18 +405,38 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
-	let enable_grandpa = !config.disable_grandpa;
+	let enable_grandpa = !config.disable_grandpa && sealing.is_none();
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let (command_sink, rpc_commands) = mpsc::channel(1024);
+	let command_sink = sealing.map(|_| command_sink);
+
+	let filter_pool: FilterPool = Arc::new(Mutex::new(BTreeMap::new()));
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let network = network.clone();
+		let filter_pool = filter_pool.clone();
+		let frontier_backend = frontier_backend.clone();
+		let is_authority = role.is_authority();
 
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
-
-			Ok(crate::rpc::create_full(deps))
+		Box::new(move |deny_unsafe, subscription_task_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				graph: pool.pool().clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+				is_authority,
+				enable_dev_signer: eth_config.enable_dev_signer,
+				network: network.clone(),
+				filter_pool: Some(filter_pool.clone()),
+				backend: frontier_backend.clone(),
+				max_past_logs: eth_config.max_past_logs,
+			};
+
+			Ok(crate::rpc::create_full(deps, subscription_task_executor))
 		})
 	};
 
@@ -233,13 +447,96 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		task_manager: &mut task_manager,
 		transaction_pool: transaction_pool.clone(),
 		rpc_extensions_builder,
-		backend,
+		backend: backend.clone(),
 		system_rpc_tx,
 		config,
 		telemetry: telemetry.as_mut(),
 	})?;
 
-	if role.is_authority() {
+	// Keeps the Frontier database in step with imported blocks.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		None,
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::from_millis(MILLISECS_PER_BLOCK),
+			client.clone(),
+			backend,
+			frontier_backend.clone(),
+		)
+		.for_each(|()| futures::future::ready(())),
+	);
+
+	// Drops filters nobody has polled for `FILTER_RETAIN_THRESHOLD` blocks.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-filter-pool",
+		None,
+		EthTask::filter_pool_task(client.clone(), filter_pool, FILTER_RETAIN_THRESHOLD),
+	);
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-schema-cache-task",
+		None,
+		EthTask::ethereum_schema_cache_task(client.clone(), frontier_backend),
+	);
+
+	if let Some(sealing) = sealing {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		// Sealed blocks still carry BABE pre-digests, claimed with the keys in the keystore, so
+		// the runtime sees the same slots and epochs as on a BABE network.
+		let authorities = client
+			.runtime_api()
+			.configuration(&BlockId::Number(0))
+			.map_err(|e| ServiceError::Other(format!("Failed to read BABE authorities: {}", e)))?
+			.genesis_authorities;
+		let consensus_data_provider = BabeConsensusDataProvider::new(
+			client.clone(),
+			keystore_container.sync_keystore(),
+			babe_link.epoch_changes().clone(),
+			authorities,
+		)
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
+
+		let commands_stream = sealing_commands(sealing, rpc_commands, &transaction_pool);
+		let client_clone = client.clone();
+
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import,
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool.pool().clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(consensus_data_provider)),
+			create_inherent_data_providers: move |_, ()| {
+				let client = client_clone.clone();
+				async move {
+					let timestamp = SlotTimestampProvider::new(client)
+						.map_err(|e| format!("Failed to create timestamp provider: {:?}", e))?;
+
+					let slot =
+						sp_consensus_babe::inherents::InherentDataProvider::new(timestamp.slot());
+
+					Ok((timestamp, slot))
+				}
+			},
+		});
+
+		// the manual seal task is considered essential, i.e. if it
+		// fails we take down the service with it.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+	} else if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -251,44 +548,41 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		let can_author_with =
 			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
-		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
-		let raw_slot_duration = slot_duration.slot_duration();
+		let slot_duration = babe_link.config().slot_duration();
 
-		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _, _>(
-			StartAuraParams {
-				slot_duration,
-				client: client.clone(),
-				select_chain,
-				block_import,
-				proposer_factory,
-				create_inherent_data_providers: move |_, ()| async move {
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+		let babe = sc_consensus_babe::start_babe(sc_consensus_babe::BabeParams {
+			keystore: keystore_container.sync_keystore(),
+			client: client.clone(),
+			select_chain,
+			env: ProposeWhileEnabled { client: client.clone(), inner: proposer_factory },
+			block_import,
+			sync_oracle: network.clone(),
+			justification_sync_link: network.clone(),
+			create_inherent_data_providers: move |_, ()| async move {
+				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
 
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_duration(
-							*timestamp,
-							raw_slot_duration,
-						);
+				let slot =
+					sp_consensus_babe::inherents::InherentDataProvider::from_timestamp_and_duration(
+						*timestamp,
+						slot_duration,
+					);
 
-					Ok((timestamp, slot))
-				},
-				force_authoring,
-				backoff_authoring_blocks,
-				keystore: keystore_container.sync_keystore(),
-				can_author_with,
-				sync_oracle: network.clone(),
-				justification_sync_link: network.clone(),
-				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
-				max_block_proposal_slot_portion: None,
-				telemetry: telemetry.as_ref().map(|x| x.handle()),
+				Ok((timestamp, slot))
 			},
-		)?;
+			force_authoring,
+			backoff_authoring_blocks,
+			babe_link,
+			can_author_with,
+			block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+			max_block_proposal_slot_portion: None,
+			telemetry: telemetry.as_ref().map(|x| x.handle()),
+		})?;
 
-		// the AURA authoring task is considered essential, i.e. if it
+		// the BABE authoring task is considered essential, i.e. if it
 		// fails we take down the service with it.
 		task_manager
 			.spawn_essential_handle()
-			.spawn_blocking("aura", Some("block-authoring"), aura);
+			.spawn_blocking("babe-proposer", Some("block-authoring"), babe);
 	}
 
 	// if the node isn't actively participating in consensus then it doesn't
@@ -1,20 +1,237 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-dvine
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as Dvine;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{traits::Currency, BoundedVec};
 use frame_system::RawOrigin;
+use sp_runtime::{traits::Zero, Percent};
+use sp_std::{vec, vec::Vec};
+
+const SEED: u32 = 0;
+
+/// Register an allowance for `sponsor` covering the most calls possible.
+fn set_allowance<T: Config>(sponsor: &T::AccountId) {
+	let calls = vec![SponsoredCall::FundGuild; T::MaxSponsoredCalls::get() as usize];
+	let calls = calls.try_into().unwrap();
+	Allowances::<T>::insert(sponsor, Allowance { calls, per_era: 5 });
+}
+
+/// Make `sponsor` pay the fees of `reader`.
+fn sponsor_reader<T: Config>(sponsor: &T::AccountId, reader: &T::AccountId) {
+	Sponsors::<T>::insert(reader, sponsor);
+	SponsoredReaders::<T>::mutate(sponsor, |readers| readers.try_push(reader.clone())).unwrap();
+	Dvine::<T>::note_sponsored_call(reader);
+}
+
+/// Create a guild whose members are `caller` and `m - 1` others, with `p` open proposals on which
+/// every other member has voted.
+fn create_guild<T: Config>(
+	caller: &T::AccountId,
+	m: u32,
+	p: u32,
+	threshold: Percent,
+) -> (GuildId, Vec<T::AccountId>) {
+	let others: Vec<T::AccountId> = (1..m).map(|i| account("member", i, SEED)).collect();
+	let mut members = others.clone();
+	members.push(caller.clone());
+	let members: BoundedVec<_, T::MaxGuildMembers> = members.try_into().unwrap();
+	let votes: BoundedVec<_, T::MaxGuildMembers> = others.clone().try_into().unwrap();
+
+	let guild_id = Dvine::<T>::next_guild_id();
+	NextGuildId::<T>::put(guild_id + 1);
+	let guild = Guild {
+		founder: caller.clone(),
+		deposit: Zero::zero(),
+		members,
+		invited: Default::default(),
+		threshold,
+		open_proposals: p,
+	};
+	Guilds::<T>::insert(guild_id, guild);
+	for index in 0..p {
+		let proposal = GuildProposal {
+			proposer: caller.clone(),
+			deposit: Zero::zero(),
+			action: GuildAction::Admit(account("candidate", index, SEED)),
+			ayes: votes.clone(),
+			nays: Default::default(),
+		};
+		GuildProposals::<T>::insert(guild_id, index, proposal);
+	}
+	GuildProposalCount::<T>::insert(guild_id, p);
+
+	(guild_id, others)
+}
+
+/// Give `who` enough to pay any deposit.
+fn fund<T: Config>(who: &T::AccountId) {
+	let balance = T::Currency::minimum_balance() * 1_000u32.into() +
+		T::GuildDeposit::get() +
+		T::GuildProposalDeposit::get();
+	T::Currency::make_free_balance_be(who, balance);
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	set_allowance {
+		let c in 0 .. T::MaxSponsoredCalls::get();
+		let calls = vec![SponsoredCall::FundGuild; c as usize];
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), calls, 5)
+	verify {
+		assert_eq!(Dvine::<T>::allowance(&caller).unwrap().calls.len() as u32, c);
+	}
+
+	remove_allowance {
+		let r in 0 .. T::MaxSponsoredReaders::get();
+		let caller: T::AccountId = whitelisted_caller();
+		set_allowance::<T>(&caller);
+		let readers: Vec<T::AccountId> = (0..r).map(|i| account("reader", i, SEED)).collect();
+		for reader in readers.iter() {
+			sponsor_reader::<T>(&caller, reader);
+		}
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Dvine::<T>::allowance(&caller).is_none());
+		assert!(readers.iter().all(|reader| Dvine::<T>::sponsor_of(reader).is_none()));
+	}
+
+	sponsor {
+		let caller: T::AccountId = whitelisted_caller();
+		set_allowance::<T>(&caller);
+		let reader: T::AccountId = account("reader", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), reader.clone())
+	verify {
+		assert!(SponsorOffers::<T>::contains_key(&caller, &reader));
+	}
+
+	// The worst case ends a sponsorship, rather than withdrawing an offer.
+	unsponsor {
+		let caller: T::AccountId = whitelisted_caller();
+		set_allowance::<T>(&caller);
+		let reader: T::AccountId = account("reader", 0, SEED);
+		sponsor_reader::<T>(&caller, &reader);
+	}: _(RawOrigin::Signed(caller), reader.clone())
+	verify {
+		assert!(Dvine::<T>::sponsor_of(&reader).is_none());
+	}
+
+	create_guild {
+		let m in 0 .. T::MaxGuildMembers::get() - 1;
+		let members: Vec<T::AccountId> = (0..m).map(|i| account("member", i, SEED)).collect();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let guild_id = Dvine::<T>::next_guild_id();
+	}: _(RawOrigin::Signed(caller), members, Percent::from_percent(50))
+	verify {
+		assert_eq!(Dvine::<T>::guild(guild_id).unwrap().invited.len() as u32, m);
+	}
+
+	fund_guild {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let (guild_id, _) = create_guild::<T>(&caller, 1, 0, Percent::from_percent(50));
+		let amount = T::Currency::minimum_balance() * 10u32.into();
+	}: _(RawOrigin::Signed(caller), guild_id, amount)
+	verify {
+		assert_eq!(T::Currency::free_balance(&Dvine::<T>::guild_account_id(guild_id)), amount);
+	}
+
+	// The worst case is a proposal to expel a member that passes right away, dropping the
+	// member's votes from every other open proposal.
+	propose_guild_action {
+		let m in 2 .. T::MaxGuildMembers::get();
+		let p in 0 .. T::MaxGuildProposals::get() - 1;
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let (guild_id, others) = create_guild::<T>(&caller, m, p, Percent::from_percent(0));
+		let action = GuildAction::Expel(others[0].clone());
+	}: _(RawOrigin::Signed(caller), guild_id, action)
+	verify {
+		assert_eq!(Dvine::<T>::guild(guild_id).unwrap().members.len() as u32, m - 1);
+	}
+
+	// The worst case is the deciding vote on a proposal to expel a member, dropping the member's
+	// votes from every other open proposal.
+	vote_guild_action {
+		let m in 2 .. T::MaxGuildMembers::get();
+		let p in 1 .. T::MaxGuildProposals::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let (guild_id, others) = create_guild::<T>(&caller, m, p, Percent::from_percent(100));
+		GuildProposals::<T>::mutate(guild_id, 0, |proposal| {
+			if let Some(proposal) = proposal {
+				proposal.action = GuildAction::Expel(others[0].clone());
+			}
+		});
+	}: _(RawOrigin::Signed(caller), guild_id, 0, true)
+	verify {
+		assert_eq!(Dvine::<T>::guild(guild_id).unwrap().members.len() as u32, m - 1);
+	}
+
+	accept_sponsor {
+		let sponsor: T::AccountId = account("sponsor", 0, SEED);
+		set_allowance::<T>(&sponsor);
+		let caller: T::AccountId = whitelisted_caller();
+		SponsorOffers::<T>::insert(&sponsor, &caller, ());
+	}: _(RawOrigin::Signed(caller.clone()), sponsor.clone())
+	verify {
+		assert_eq!(Dvine::<T>::sponsor_of(&caller), Some(sponsor));
+	}
+
+	leave_sponsor {
+		let sponsor: T::AccountId = account("sponsor", 0, SEED);
+		set_allowance::<T>(&sponsor);
+		let caller: T::AccountId = whitelisted_caller();
+		sponsor_reader::<T>(&sponsor, &caller);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Dvine::<T>::sponsor_of(&caller).is_none());
+	}
+
+	accept_guild_invite {
+		let m in 1 .. T::MaxGuildMembers::get() - 1;
+		let founder: T::AccountId = account("founder", 0, SEED);
+		let (guild_id, _) = create_guild::<T>(&founder, m, 0, Percent::from_percent(50));
+		let caller: T::AccountId = whitelisted_caller();
+		Guilds::<T>::mutate(guild_id, |guild| {
+			if let Some(guild) = guild {
+				guild.invited.try_push(caller.clone()).unwrap();
+			}
+		});
+	}: _(RawOrigin::Signed(caller.clone()), guild_id)
+	verify {
+		assert!(Dvine::<T>::guild(guild_id).unwrap().members.contains(&caller));
+	}
+
+	// The worst case is leaving a guild where every open proposal is a spend that the other
+	// members have approved, but that only passes once the caller is gone.
+	leave_guild {
+		let m in 2 .. T::MaxGuildMembers::get();
+		let p in 0 .. T::MaxGuildProposals::get();
+		let founder: T::AccountId = account("founder", 0, SEED);
+		let (guild_id, _) = create_guild::<T>(&founder, m, p, Percent::from_percent(100));
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		Guilds::<T>::mutate(guild_id, |guild| {
+			if let Some(guild) = guild {
+				guild.members.retain(|member| member != &founder);
+				guild.members.try_push(caller.clone()).unwrap();
+			}
+		});
+		for index in 0..p {
+			GuildProposals::<T>::mutate(guild_id, index, |proposal| {
+				if let Some(proposal) = proposal {
+					proposal.action =
+						GuildAction::Spend { to: founder.clone(), amount: Zero::zero() };
+				}
+			});
+		}
+	}: _(RawOrigin::Signed(caller.clone()), guild_id)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(!Dvine::<T>::guild(guild_id).unwrap().members.contains(&caller));
+		assert_eq!(Dvine::<T>::guild(guild_id).unwrap().open_proposals, 0);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(Dvine, crate::mock::new_test_ext(), crate::mock::Test);
 }
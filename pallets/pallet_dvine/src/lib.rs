@@ -14,47 +14,379 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod payment;
+pub mod weights;
+pub use payment::ChargeSponsoredTransactionPayment;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use crate::WeightInfo;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, ReservableCurrency},
+		PalletId,
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{AccountIdConversion, CheckedDiv, Saturating, Zero},
+		Percent,
+	};
+	use sp_std::vec::Vec;
+
+	/// The current storage version.
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	/// Identifier of an author guild.
+	pub type GuildId = u32;
+
+	/// Index of a proposal within a guild.
+	pub type ProposalIndex = u32;
+
+	/// Identifier of a work a guild features, such as the hash of its content.
+	pub type WorkId = [u8; 32];
+
+	pub(crate) type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The number of blocks in a sponsorship era. Sponsored call counts reset every era.
+		#[pallet::constant]
+		type SponsorshipEra: Get<Self::BlockNumber>;
+
+		/// The maximum number of calls a single sponsorship allowance can cover.
+		#[pallet::constant]
+		type MaxSponsoredCalls: Get<u32>;
+
+		/// The maximum number of readers a single sponsor can pay the fees of.
+		#[pallet::constant]
+		type MaxSponsoredReaders: Get<u32>;
+
+		/// The currency guild treasuries hold and guild deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the founder of a guild until the guild closes.
+		#[pallet::constant]
+		type GuildDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved from the proposer of a guild proposal until it is decided.
+		#[pallet::constant]
+		type GuildProposalDeposit: Get<BalanceOf<Self>>;
+
+		/// The pallet id guild treasury accounts are derived from.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of members of a guild.
+		#[pallet::constant]
+		type MaxGuildMembers: Get<u32>;
+
+		/// The maximum number of open proposals a guild can have at once.
+		#[pallet::constant]
+		type MaxGuildProposals: Get<u32>;
+
+		/// The maximum number of works a guild can feature at once.
+		#[pallet::constant]
+		type MaxFeaturedWorks: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The calls of this pallet a sponsor can pay the fees of.
+	///
+	/// Allowances store these rather than call indices, so reordering the calls of this pallet
+	/// leaves them valid. New variants take the next free codec index.
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum SponsoredCall {
+		#[codec(index = 0)]
+		CreateGuild,
+		#[codec(index = 1)]
+		FundGuild,
+		#[codec(index = 2)]
+		ProposeGuildAction,
+		#[codec(index = 3)]
+		VoteGuildAction,
+		#[codec(index = 4)]
+		AcceptGuildInvite,
+		#[codec(index = 5)]
+		LeaveGuild,
+	}
+
+	impl SponsoredCall {
+		/// The kind of `call`, if a sponsor can pay for it.
+		pub fn of<T: Config>(call: &Call<T>) -> Option<Self> {
+			match call {
+				Call::create_guild { .. } => Some(Self::CreateGuild),
+				Call::fund_guild { .. } => Some(Self::FundGuild),
+				Call::propose_guild_action { .. } => Some(Self::ProposeGuildAction),
+				Call::vote_guild_action { .. } => Some(Self::VoteGuildAction),
+				Call::accept_guild_invite { .. } => Some(Self::AcceptGuildInvite),
+				Call::leave_guild { .. } => Some(Self::LeaveGuild),
+				_ => None,
+			}
+		}
+	}
+
+	/// The allowance a sponsor extends to the readers it sponsors.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Allowance<T: Config> {
+		/// The calls the sponsor pays fees for.
+		pub calls: BoundedVec<SponsoredCall, T::MaxSponsoredCalls>,
+		/// How many sponsored calls each reader may make per sponsorship era.
+		pub per_era: u32,
+	}
+
+	// Deriving `MaxEncodedLen` would bound `T` itself, which runtimes don't implement it for.
+	impl<T: Config> MaxEncodedLen for Allowance<T> {
+		fn max_encoded_len() -> usize {
+			BoundedVec::<SponsoredCall, T::MaxSponsoredCalls>::max_encoded_len()
+				.saturating_add(u32::max_encoded_len())
+		}
+	}
+
+	/// A group of writers pooling funds from a shared treasury account.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Guild<T: Config> {
+		/// The account that created the guild. Its deposit is returned when the guild closes.
+		pub founder: T::AccountId,
+		/// The deposit reserved from the founder.
+		pub deposit: BalanceOf<T>,
+		/// The guild's members. Only members can raise and vote on proposals.
+		pub members: BoundedVec<T::AccountId, T::MaxGuildMembers>,
+		/// Accounts invited to join the guild that have not accepted yet. They count towards
+		/// `MaxGuildMembers`.
+		pub invited: BoundedVec<T::AccountId, T::MaxGuildMembers>,
+		/// The share of members that must approve a proposal for it to pass.
+		pub threshold: Percent,
+		/// The number of proposals waiting for a decision.
+		pub open_proposals: u32,
+	}
+
+	impl<T: Config> MaxEncodedLen for Guild<T> {
+		fn max_encoded_len() -> usize {
+			let accounts = BoundedVec::<T::AccountId, T::MaxGuildMembers>::max_encoded_len();
+			T::AccountId::max_encoded_len()
+				.saturating_add(BalanceOf::<T>::max_encoded_len())
+				.saturating_add(accounts.saturating_mul(2))
+				.saturating_add(Percent::max_encoded_len())
+				.saturating_add(u32::max_encoded_len())
+		}
+	}
+
+	/// What a guild does once a proposal passes.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum GuildAction<AccountId, Balance> {
+		/// Pay `amount` from the guild treasury to `to`.
+		Spend { to: AccountId, amount: Balance },
+		/// Invite a new member, who joins once it accepts.
+		Admit(AccountId),
+		/// Expel a member, or withdraw an invitation.
+		Expel(AccountId),
+		/// Feature `work`, written by the member `author`.
+		Feature { author: AccountId, work: WorkId },
+		/// Stop featuring a work.
+		Unfeature(WorkId),
+	}
+
+	/// A proposal raised by a guild member and the votes cast on it so far.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct GuildProposal<T: Config> {
+		/// The member who raised the proposal.
+		pub proposer: T::AccountId,
+		/// The deposit reserved from the proposer.
+		pub deposit: BalanceOf<T>,
+		/// The action taken if the proposal passes.
+		pub action: GuildAction<T::AccountId, BalanceOf<T>>,
+		/// Members who voted for the proposal.
+		pub ayes: BoundedVec<T::AccountId, T::MaxGuildMembers>,
+		/// Members who voted against the proposal.
+		pub nays: BoundedVec<T::AccountId, T::MaxGuildMembers>,
+	}
+
+	impl<T: Config> MaxEncodedLen for GuildProposal<T> {
+		fn max_encoded_len() -> usize {
+			let votes = BoundedVec::<T::AccountId, T::MaxGuildMembers>::max_encoded_len();
+			T::AccountId::max_encoded_len()
+				.saturating_add(BalanceOf::<T>::max_encoded_len())
+				.saturating_add(GuildAction::<T::AccountId, BalanceOf<T>>::max_encoded_len())
+				.saturating_add(votes.saturating_mul(2))
+		}
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/v3/runtime/storage
+
+	/// The allowance registered by each sponsor.
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
-	// Learn more about declaring storage items:
-	// https://docs.substrate.io/v3/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	#[pallet::getter(fn allowance)]
+	pub type Allowances<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Allowance<T>>;
+
+	/// The sponsor paying the fees of each sponsored reader.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsor_of)]
+	pub type Sponsors<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// The readers each sponsor pays the fees of.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_readers)]
+	pub type SponsoredReaders<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxSponsoredReaders>,
+		ValueQuery,
+	>;
+
+	/// The sponsorships offered to readers that have not accepted them yet, by sponsor and reader.
+	#[pallet::storage]
+	pub type SponsorOffers<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The sponsorship era a reader last made a sponsored call in, and how many it has made in
+	/// that era.
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_usage)]
+	pub type SponsoredUsage<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// The id the next guild is created with.
+	#[pallet::storage]
+	#[pallet::getter(fn next_guild_id)]
+	pub type NextGuildId<T> = StorageValue<_, GuildId, ValueQuery>;
+
+	/// The guilds created so far.
+	#[pallet::storage]
+	#[pallet::getter(fn guild)]
+	pub type Guilds<T: Config> = StorageMap<_, Twox64Concat, GuildId, Guild<T>>;
+
+	/// The index the next proposal of each guild is raised with.
+	#[pallet::storage]
+	#[pallet::getter(fn guild_proposal_count)]
+	pub type GuildProposalCount<T> =
+		StorageMap<_, Twox64Concat, GuildId, ProposalIndex, ValueQuery>;
+
+	/// The open proposals of each guild.
+	#[pallet::storage]
+	#[pallet::getter(fn guild_proposal)]
+	pub type GuildProposals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, GuildId, Twox64Concat, ProposalIndex, GuildProposal<T>>;
+
+	/// The works each guild features, with their authors, in the order they were featured.
+	#[pallet::storage]
+	#[pallet::getter(fn featured_works)]
+	pub type FeaturedWorks<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		GuildId,
+		BoundedVec<(WorkId, T::AccountId), T::MaxFeaturedWorks>,
+		ValueQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// A sponsor registered or replaced its allowance. [sponsor, per_era]
+		AllowanceSet(T::AccountId, u32),
+		/// A sponsor removed its allowance. [sponsor]
+		AllowanceRemoved(T::AccountId),
+		/// A sponsor offered to pay the fees of a reader. [sponsor, reader]
+		SponsorshipOffered(T::AccountId, T::AccountId),
+		/// A reader accepted a sponsorship, and the sponsor started paying its fees. [sponsor,
+		/// reader]
+		ReaderSponsored(T::AccountId, T::AccountId),
+		/// A sponsorship ended or an offer was withdrawn. [sponsor, reader]
+		ReaderUnsponsored(T::AccountId, T::AccountId),
+		/// A guild was created. [guild_id, founder]
+		GuildCreated(GuildId, T::AccountId),
+		/// Funds were paid into a guild treasury. [guild_id, who, amount]
+		GuildFunded(GuildId, T::AccountId, BalanceOf<T>),
+		/// A member raised a guild proposal. [guild_id, proposal_index, proposer]
+		GuildProposed(GuildId, ProposalIndex, T::AccountId),
+		/// A member voted on a guild proposal. [guild_id, proposal_index, who, approve]
+		GuildVoted(GuildId, ProposalIndex, T::AccountId, bool),
+		/// A guild proposal passed and its action was carried out. [guild_id, proposal_index,
+		/// result]
+		GuildProposalExecuted(GuildId, ProposalIndex, DispatchResult),
+		/// A guild proposal can no longer pass and was dropped. [guild_id, proposal_index]
+		GuildProposalRejected(GuildId, ProposalIndex),
+		/// A member joined a guild. [guild_id, who]
+		GuildMemberAdmitted(GuildId, T::AccountId),
+		/// A member was expelled from a guild, or its invitation withdrawn. [guild_id, who]
+		GuildMemberExpelled(GuildId, T::AccountId),
+		/// An account was invited to join a guild. [guild_id, who]
+		GuildMemberInvited(GuildId, T::AccountId),
+		/// A member left a guild. [guild_id, who]
+		GuildMemberLeft(GuildId, T::AccountId),
+		/// The last member left a guild, and the guild was closed. [guild_id]
+		GuildClosed(GuildId),
+		/// A guild proposal was dropped because its proposer left the guild. [guild_id,
+		/// proposal_index]
+		GuildProposalWithdrawn(GuildId, ProposalIndex),
+		/// A guild featured a work by one of its members. [guild_id, work, author]
+		GuildWorkFeatured(GuildId, WorkId, T::AccountId),
+		/// A guild stopped featuring a work. [guild_id, work]
+		GuildWorkUnfeatured(GuildId, WorkId),
 	}
 
 	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
-		StorageOverflow,
+		/// The allowance lists more calls than `MaxSponsoredCalls`.
+		TooManySponsoredCalls,
+		/// The sponsor has not registered an allowance.
+		NoAllowance,
+		/// The reader is already sponsored.
+		AlreadySponsored,
+		/// The caller is not the reader's sponsor and has no offer pending for it.
+		NotSponsor,
+		/// The sponsor has not offered to pay the reader's fees.
+		NoSponsorOffer,
+		/// The sponsor already pays the fees of `MaxSponsoredReaders` readers.
+		TooManySponsoredReaders,
+		/// The caller is not sponsored.
+		NotSponsored,
+		/// No guild exists with the given id.
+		UnknownGuild,
+		/// The guild already has `MaxGuildMembers` members.
+		GuildFull,
+		/// The account is not a member of the guild.
+		NotGuildMember,
+		/// The account is already a member of the guild.
+		AlreadyGuildMember,
+		/// The last member of a guild cannot be expelled.
+		LastGuildMember,
+		/// The guild already has `MaxGuildProposals` open proposals.
+		TooManyGuildProposals,
+		/// No open proposal exists with the given index.
+		UnknownGuildProposal,
+		/// The member has already voted on the proposal.
+		DuplicateGuildVote,
+		/// The account has not been invited to join the guild.
+		NotGuildInvitee,
+		/// The guild already features the work.
+		AlreadyFeatured,
+		/// The guild does not feature the work.
+		NotFeatured,
+		/// The guild already features `MaxFeaturedWorks` works.
+		TooManyFeaturedWorks,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -62,41 +394,531 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://docs.substrate.io/v3/runtime/origins
+		/// Register or replace the allowance the caller extends to the readers it sponsors.
+		///
+		/// `calls` are the calls the caller pays fees for, and `per_era` caps how many of them each
+		/// reader may make per sponsorship era.
+		#[pallet::weight(T::WeightInfo::set_allowance(calls.len() as u32))]
+		pub fn set_allowance(
+			origin: OriginFor<T>,
+			calls: Vec<SponsoredCall>,
+			per_era: u32,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			let calls: BoundedVec<SponsoredCall, T::MaxSponsoredCalls> =
+				calls.try_into().map_err(|_| Error::<T>::TooManySponsoredCalls)?;
+			<Allowances<T>>::insert(&sponsor, Allowance { calls, per_era });
+
+			Self::deposit_event(Event::AllowanceSet(sponsor, per_era));
+			Ok(())
+		}
+
+		/// Remove the caller's allowance and end every sponsorship under it. Its readers pay
+		/// their own fees from now on.
+		#[pallet::weight(T::WeightInfo::remove_allowance(T::MaxSponsoredReaders::get()))]
+		pub fn remove_allowance(origin: OriginFor<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			ensure!(<Allowances<T>>::contains_key(&sponsor), Error::<T>::NoAllowance);
+			<Allowances<T>>::remove(&sponsor);
+			for reader in <SponsoredReaders<T>>::take(&sponsor) {
+				<Sponsors<T>>::remove(&reader);
+				<SponsoredUsage<T>>::remove(&reader);
+				Self::deposit_event(Event::ReaderUnsponsored(sponsor.clone(), reader));
+			}
+
+			Self::deposit_event(Event::AllowanceRemoved(sponsor));
+			Ok(())
+		}
+
+		/// Offer to pay the fees of `reader` under the caller's allowance.
+		///
+		/// The sponsorship starts once the reader accepts it with `accept_sponsor`.
+		#[pallet::weight(T::WeightInfo::sponsor())]
+		pub fn sponsor(origin: OriginFor<T>, reader: T::AccountId) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			ensure!(<Allowances<T>>::contains_key(&sponsor), Error::<T>::NoAllowance);
+			ensure!(
+				Self::sponsor_of(&reader).as_ref() != Some(&sponsor),
+				Error::<T>::AlreadySponsored
+			);
+			<SponsorOffers<T>>::insert(&sponsor, &reader, ());
+
+			Self::deposit_event(Event::SponsorshipOffered(sponsor, reader));
+			Ok(())
+		}
+
+		/// Stop paying the fees of `reader`, or withdraw an offer to do so that `reader` has not
+		/// accepted yet.
+		#[pallet::weight(T::WeightInfo::unsponsor())]
+		pub fn unsponsor(origin: OriginFor<T>, reader: T::AccountId) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			if Self::sponsor_of(&reader).as_ref() == Some(&sponsor) {
+				Self::end_sponsorship(&sponsor, &reader);
+			} else {
+				ensure!(
+					<SponsorOffers<T>>::contains_key(&sponsor, &reader),
+					Error::<T>::NotSponsor
+				);
+				<SponsorOffers<T>>::remove(&sponsor, &reader);
+			}
+
+			Self::deposit_event(Event::ReaderUnsponsored(sponsor, reader));
+			Ok(())
+		}
+
+		/// Create a guild with the caller as its only member, and invite `members` to join it.
+		///
+		/// Proposals pass once at least `threshold` of the members approve them. `GuildDeposit` is
+		/// reserved from the caller until the guild closes.
+		#[pallet::weight(T::WeightInfo::create_guild(members.len() as u32))]
+		pub fn create_guild(
+			origin: OriginFor<T>,
+			members: Vec<T::AccountId>,
+			threshold: Percent,
+		) -> DispatchResult {
+			let founder = ensure_signed(origin)?;
+
+			let mut invited = members;
+			invited.sort();
+			invited.dedup();
+			invited.retain(|who| who != &founder);
+			ensure!(invited.len() < T::MaxGuildMembers::get() as usize, Error::<T>::GuildFull);
+			let invited: BoundedVec<T::AccountId, T::MaxGuildMembers> =
+				invited.try_into().map_err(|_| Error::<T>::GuildFull)?;
+			let mut members = BoundedVec::default();
+			members.try_push(founder.clone()).map_err(|_| Error::<T>::GuildFull)?;
+
+			let deposit = T::GuildDeposit::get();
+			T::Currency::reserve(&founder, deposit)?;
+
+			let guild_id = Self::next_guild_id();
+			<NextGuildId<T>>::put(guild_id.saturating_add(1));
+			let guild = Guild {
+				founder: founder.clone(),
+				deposit,
+				members,
+				invited: invited.clone(),
+				threshold,
+				open_proposals: 0,
+			};
+			<Guilds<T>>::insert(guild_id, guild);
+
+			Self::deposit_event(Event::GuildCreated(guild_id, founder));
+			for who in invited {
+				Self::deposit_event(Event::GuildMemberInvited(guild_id, who));
+			}
+			Ok(())
+		}
+
+		/// Pay `amount` from the caller into the treasury of `guild_id`.
+		#[pallet::weight(T::WeightInfo::fund_guild())]
+		pub fn fund_guild(
+			origin: OriginFor<T>,
+			guild_id: GuildId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(<Guilds<T>>::contains_key(guild_id), Error::<T>::UnknownGuild);
+			T::Currency::transfer(
+				&who,
+				&Self::guild_account_id(guild_id),
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::GuildFunded(guild_id, who, amount));
+			Ok(())
+		}
+
+		/// Propose that `guild_id` takes `action`. The proposer's approval is counted right away.
+		///
+		/// `GuildProposalDeposit` is reserved from the caller until the proposal is decided.
+		#[pallet::weight(T::WeightInfo::propose_guild_action(
+			T::MaxGuildMembers::get(),
+			T::MaxGuildProposals::get(),
+		))]
+		pub fn propose_guild_action(
+			origin: OriginFor<T>,
+			guild_id: GuildId,
+			action: GuildAction<T::AccountId, BalanceOf<T>>,
+		) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+
+			let mut guild = Self::guild(guild_id).ok_or(Error::<T>::UnknownGuild)?;
+			ensure!(guild.members.contains(&proposer), Error::<T>::NotGuildMember);
+			ensure!(
+				guild.open_proposals < T::MaxGuildProposals::get(),
+				Error::<T>::TooManyGuildProposals
+			);
+
+			let deposit = T::GuildProposalDeposit::get();
+			T::Currency::reserve(&proposer, deposit)?;
+
+			let mut ayes = BoundedVec::default();
+			ayes.try_push(proposer.clone()).map_err(|_| Error::<T>::GuildFull)?;
+			let proposal = GuildProposal {
+				proposer: proposer.clone(),
+				deposit,
+				action,
+				ayes,
+				nays: Default::default(),
+			};
+
+			let index = Self::guild_proposal_count(guild_id);
+			<GuildProposalCount<T>>::insert(guild_id, index.saturating_add(1));
+			guild.open_proposals += 1;
+
+			Self::deposit_event(Event::GuildProposed(guild_id, index, proposer));
+			Self::decide(guild_id, index, &mut guild, proposal);
+			<Guilds<T>>::insert(guild_id, guild);
+			Ok(())
+		}
+
+		/// Vote on an open proposal of `guild_id`.
+		///
+		/// The proposal is carried out as soon as enough members approve it, and dropped as soon as
+		/// it can no longer pass.
+		#[pallet::weight(T::WeightInfo::vote_guild_action(
+			T::MaxGuildMembers::get(),
+			T::MaxGuildProposals::get(),
+		))]
+		pub fn vote_guild_action(
+			origin: OriginFor<T>,
+			guild_id: GuildId,
+			index: ProposalIndex,
+			approve: bool,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// Update storage.
-			<Something<T>>::put(something);
+			let mut guild = Self::guild(guild_id).ok_or(Error::<T>::UnknownGuild)?;
+			ensure!(guild.members.contains(&who), Error::<T>::NotGuildMember);
+			let mut proposal =
+				Self::guild_proposal(guild_id, index).ok_or(Error::<T>::UnknownGuildProposal)?;
+			ensure!(
+				!proposal.ayes.contains(&who) && !proposal.nays.contains(&who),
+				Error::<T>::DuplicateGuildVote
+			);
+
+			let votes = if approve { &mut proposal.ayes } else { &mut proposal.nays };
+			votes.try_push(who.clone()).map_err(|_| Error::<T>::GuildFull)?;
+
+			Self::deposit_event(Event::GuildVoted(guild_id, index, who, approve));
+			Self::decide(guild_id, index, &mut guild, proposal);
+			<Guilds<T>>::insert(guild_id, guild);
+			Ok(())
+		}
+
+		/// Accept the offer of `sponsor` to pay the caller's fees.
+		///
+		/// A reader has at most one sponsor, so a sponsored reader has to leave its sponsor first.
+		#[pallet::weight(T::WeightInfo::accept_sponsor())]
+		pub fn accept_sponsor(origin: OriginFor<T>, sponsor: T::AccountId) -> DispatchResult {
+			let reader = ensure_signed(origin)?;
+
+			ensure!(
+				<SponsorOffers<T>>::contains_key(&sponsor, &reader),
+				Error::<T>::NoSponsorOffer
+			);
+			ensure!(<Allowances<T>>::contains_key(&sponsor), Error::<T>::NoAllowance);
+			ensure!(!<Sponsors<T>>::contains_key(&reader), Error::<T>::AlreadySponsored);
+			<SponsoredReaders<T>>::try_mutate(&sponsor, |readers| readers.try_push(reader.clone()))
+				.map_err(|_| Error::<T>::TooManySponsoredReaders)?;
+			<SponsorOffers<T>>::remove(&sponsor, &reader);
+			<Sponsors<T>>::insert(&reader, &sponsor);
 
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
-			// Return a successful DispatchResultWithPostInfo
+			Self::deposit_event(Event::ReaderSponsored(sponsor, reader));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
-
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
-				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
+		/// End the sponsorship paying the caller's fees.
+		#[pallet::weight(T::WeightInfo::leave_sponsor())]
+		pub fn leave_sponsor(origin: OriginFor<T>) -> DispatchResult {
+			let reader = ensure_signed(origin)?;
+
+			let sponsor = Self::sponsor_of(&reader).ok_or(Error::<T>::NotSponsored)?;
+			Self::end_sponsorship(&sponsor, &reader);
+
+			Self::deposit_event(Event::ReaderUnsponsored(sponsor, reader));
+			Ok(())
+		}
+
+		/// Accept an invitation to join `guild_id`.
+		#[pallet::weight(T::WeightInfo::accept_guild_invite(T::MaxGuildMembers::get()))]
+		pub fn accept_guild_invite(origin: OriginFor<T>, guild_id: GuildId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut guild = Self::guild(guild_id).ok_or(Error::<T>::UnknownGuild)?;
+			let position = guild
+				.invited
+				.iter()
+				.position(|invitee| invitee == &who)
+				.ok_or(Error::<T>::NotGuildInvitee)?;
+			guild.invited.remove(position);
+			guild.members.try_push(who.clone()).map_err(|_| Error::<T>::GuildFull)?;
+			<Guilds<T>>::insert(guild_id, guild);
+
+			Self::deposit_event(Event::GuildMemberAdmitted(guild_id, who));
+			Ok(())
+		}
+
+		/// Leave `guild_id`. Votes the caller cast on open proposals no longer count.
+		///
+		/// When the last member leaves, the guild closes: what is left in its treasury is paid to
+		/// that member, open proposals are dropped and every deposit is returned.
+		#[pallet::weight(T::WeightInfo::leave_guild(
+			T::MaxGuildMembers::get(),
+			T::MaxGuildProposals::get(),
+		))]
+		pub fn leave_guild(origin: OriginFor<T>, guild_id: GuildId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut guild = Self::guild(guild_id).ok_or(Error::<T>::UnknownGuild)?;
+			ensure!(guild.members.contains(&who), Error::<T>::NotGuildMember);
+
+			if guild.members.len() == 1 {
+				Self::close_guild(guild_id, guild, &who)?;
+			} else {
+				Self::remove_member(guild_id, &mut guild, &who);
+				<Guilds<T>>::insert(guild_id, guild);
+				Self::deposit_event(Event::GuildMemberLeft(guild_id, who));
+			}
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The sponsor paying the fee of `call` on behalf of `reader`, if any.
+		///
+		/// A sponsor pays when its allowance covers the call and the reader has not used up the
+		/// allowance for the current sponsorship era. A pending offer also pays for the reader
+		/// accepting it, so that new readers need no funds to get started.
+		pub fn sponsor_for(reader: &T::AccountId, call: &Call<T>) -> Option<T::AccountId> {
+			if let Call::accept_sponsor { sponsor } = call {
+				return Self::offer_covers(sponsor, reader).then(|| sponsor.clone())
+			}
+
+			let sponsor = Self::sponsor_of(reader)?;
+			let allowance = Self::allowance(&sponsor)?;
+
+			let kind = SponsoredCall::of(call)?;
+			if !allowance.calls.contains(&kind) {
+				return None
+			}
+
+			let (era, used) = Self::sponsored_usage(reader);
+			if era == Self::sponsorship_era() && used >= allowance.per_era {
+				return None
+			}
+
+			Some(sponsor)
+		}
+
+		/// Whether the offer of `sponsor` pays for `reader` accepting it, which is only the case
+		/// when accepting it would succeed.
+		fn offer_covers(sponsor: &T::AccountId, reader: &T::AccountId) -> bool {
+			<SponsorOffers<T>>::contains_key(sponsor, reader) &&
+				<Allowances<T>>::contains_key(sponsor) &&
+				!<Sponsors<T>>::contains_key(reader) &&
+				<SponsoredReaders<T>>::decode_len(sponsor).unwrap_or(0) <
+					T::MaxSponsoredReaders::get() as usize
+		}
+
+		/// Count a sponsored call made by `reader` against its allowance for the current era.
+		///
+		/// Accepting an offer is not counted, as the reader is not sponsored yet.
+		pub fn note_sponsored_call(reader: &T::AccountId) {
+			if !<Sponsors<T>>::contains_key(reader) {
+				return
+			}
+			let current = Self::sponsorship_era();
+			<SponsoredUsage<T>>::mutate(reader, |(era, used)| {
+				if *era != current {
+					*era = current;
+					*used = 0;
+				}
+				*used = used.saturating_add(1);
+			});
+		}
+
+		/// Stop `sponsor` paying the fees of `reader`.
+		fn end_sponsorship(sponsor: &T::AccountId, reader: &T::AccountId) {
+			<Sponsors<T>>::remove(reader);
+			<SponsoredUsage<T>>::remove(reader);
+			<SponsoredReaders<T>>::mutate(sponsor, |readers| readers.retain(|r| r != reader));
+		}
+
+		/// The account holding the treasury of `guild_id`.
+		pub fn guild_account_id(guild_id: GuildId) -> T::AccountId {
+			T::PalletId::get().into_sub_account(guild_id)
+		}
+
+		/// Carry out or drop `proposal` if its votes decide it, and store it otherwise.
+		fn decide(
+			guild_id: GuildId,
+			index: ProposalIndex,
+			guild: &mut Guild<T>,
+			proposal: GuildProposal<T>,
+		) {
+			let members = guild.members.len() as u32;
+			let needed = guild.threshold.mul_ceil(members).max(1);
+
+			if proposal.ayes.len() as u32 >= needed {
+				<GuildProposals<T>>::remove(guild_id, index);
+				guild.open_proposals = guild.open_proposals.saturating_sub(1);
+				T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+				let result = Self::execute(guild_id, guild, proposal.action);
+				Self::deposit_event(Event::GuildProposalExecuted(guild_id, index, result));
+			} else if members.saturating_sub(proposal.nays.len() as u32) < needed {
+				<GuildProposals<T>>::remove(guild_id, index);
+				guild.open_proposals = guild.open_proposals.saturating_sub(1);
+				T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+				Self::deposit_event(Event::GuildProposalRejected(guild_id, index));
+			} else {
+				<GuildProposals<T>>::insert(guild_id, index, proposal);
+			}
+		}
+
+		/// Carry out a guild action that passed.
+		fn execute(
+			guild_id: GuildId,
+			guild: &mut Guild<T>,
+			action: GuildAction<T::AccountId, BalanceOf<T>>,
+		) -> DispatchResult {
+			match action {
+				GuildAction::Spend { to, amount } => T::Currency::transfer(
+					&Self::guild_account_id(guild_id),
+					&to,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				),
+				GuildAction::Admit(who) => {
+					ensure!(
+						!guild.members.contains(&who) && !guild.invited.contains(&who),
+						Error::<T>::AlreadyGuildMember
+					);
+					ensure!(
+						guild.members.len() + guild.invited.len() <
+							T::MaxGuildMembers::get() as usize,
+						Error::<T>::GuildFull
+					);
+					guild.invited.try_push(who.clone()).map_err(|_| Error::<T>::GuildFull)?;
+
+					Self::deposit_event(Event::GuildMemberInvited(guild_id, who));
+					Ok(())
+				},
+				GuildAction::Expel(who) => {
+					if let Some(position) = guild.invited.iter().position(|invitee| invitee == &who)
+					{
+						guild.invited.remove(position);
+					} else {
+						ensure!(guild.members.contains(&who), Error::<T>::NotGuildMember);
+						ensure!(guild.members.len() > 1, Error::<T>::LastGuildMember);
+						Self::remove_member(guild_id, guild, &who);
+					}
+
+					Self::deposit_event(Event::GuildMemberExpelled(guild_id, who));
+					Ok(())
+				},
+				GuildAction::Feature { author, work } => {
+					ensure!(guild.members.contains(&author), Error::<T>::NotGuildMember);
+					<FeaturedWorks<T>>::try_mutate(guild_id, |featured| -> DispatchResult {
+						ensure!(
+							!featured.iter().any(|(featured, _)| featured == &work),
+							Error::<T>::AlreadyFeatured
+						);
+						featured
+							.try_push((work, author.clone()))
+							.map_err(|_| Error::<T>::TooManyFeaturedWorks)?;
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::GuildWorkFeatured(guild_id, work, author));
+					Ok(())
+				},
+				GuildAction::Unfeature(work) => {
+					<FeaturedWorks<T>>::try_mutate(guild_id, |featured| -> DispatchResult {
+						let position = featured
+							.iter()
+							.position(|(featured, _)| featured == &work)
+							.ok_or(Error::<T>::NotFeatured)?;
+						featured.remove(position);
+						Ok(())
+					})?;
+
+					Self::deposit_event(Event::GuildWorkUnfeatured(guild_id, work));
 					Ok(())
 				},
 			}
 		}
+
+		/// Remove `who` from the members of `guild_id`, stop featuring its works, drop the
+		/// proposals it raised and decide the other open proposals again without its votes and
+		/// against the smaller membership.
+		fn remove_member(guild_id: GuildId, guild: &mut Guild<T>, who: &T::AccountId) {
+			guild.members.retain(|member| member != who);
+			<FeaturedWorks<T>>::mutate(guild_id, |featured| {
+				featured.retain(|(work, author)| {
+					if author != who {
+						return true
+					}
+					Self::deposit_event(Event::GuildWorkUnfeatured(guild_id, *work));
+					false
+				})
+			});
+
+			let open: Vec<_> = <GuildProposals<T>>::iter_keys(guild_id).collect();
+			for index in open {
+				// Deciding a proposal can expel another member and decide this one first.
+				let mut proposal = match Self::guild_proposal(guild_id, index) {
+					Some(proposal) => proposal,
+					None => continue,
+				};
+				if &proposal.proposer == who {
+					<GuildProposals<T>>::remove(guild_id, index);
+					guild.open_proposals = guild.open_proposals.saturating_sub(1);
+					T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+					Self::deposit_event(Event::GuildProposalWithdrawn(guild_id, index));
+					continue
+				}
+				proposal.ayes.retain(|voter| voter != who);
+				proposal.nays.retain(|voter| voter != who);
+				Self::decide(guild_id, index, guild, proposal);
+			}
+		}
+
+		/// Close `guild_id` as its last member, `who`, leaves it.
+		fn close_guild(guild_id: GuildId, guild: Guild<T>, who: &T::AccountId) -> DispatchResult {
+			let treasury = Self::guild_account_id(guild_id);
+			T::Currency::transfer(
+				&treasury,
+				who,
+				T::Currency::free_balance(&treasury),
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			for (_, proposal) in <GuildProposals<T>>::drain_prefix(guild_id) {
+				T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+			}
+			<GuildProposalCount<T>>::remove(guild_id);
+			<FeaturedWorks<T>>::remove(guild_id);
+			<Guilds<T>>::remove(guild_id);
+			T::Currency::unreserve(&guild.founder, guild.deposit);
+
+			Self::deposit_event(Event::GuildMemberLeft(guild_id, who.clone()));
+			Self::deposit_event(Event::GuildClosed(guild_id));
+			Ok(())
+		}
+
+		/// The index of the current sponsorship era.
+		fn sponsorship_era() -> T::BlockNumber {
+			<frame_system::Pallet<T>>::block_number()
+				.checked_div(&T::SponsorshipEra::get())
+				.unwrap_or_else(Zero::zero)
+		}
 	}
 }
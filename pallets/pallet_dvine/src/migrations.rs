@@ -0,0 +1,67 @@
+//! Storage migrations for `pallet_dvine`.
+//!
+//! Each migration checks the on-chain storage version and only runs against the version it
+//! migrates from, so it is a no-op once applied. Add the migrations to the runtime's `Executive`
+//! in the upgrade that ships them, and remove them once every chain has been upgraded.
+
+use super::*;
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::{ensure, traits::OnRuntimeUpgradeHelpersExt};
+
+/// Version 1 replaces the node template's storage with the sponsorship and guild storage.
+pub mod v1 {
+	use super::*;
+
+	/// Removes the template `Something` value.
+	///
+	/// Sponsorships and guilds were added together with this version, so there is nothing else
+	/// to translate.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			frame_support::storage::migration::remove_storage_prefix(
+				<Pallet<T>>::name().as_bytes(),
+				b"Something",
+				&[],
+			);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			Self::set_temp_storage(Pallet::<T>::on_chain_storage_version() == 0, "from_template");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+			ensure!(
+				!frame_support::storage::migration::have_storage_value(
+					<Pallet<T>>::name().as_bytes(),
+					b"Something",
+					&[],
+				),
+				"`Something` is still set"
+			);
+			if Self::get_temp_storage::<bool>("from_template").unwrap_or_default() {
+				ensure!(<Allowances<T>>::iter_keys().next().is_none(), "allowances appeared");
+				ensure!(<Guilds<T>>::iter_keys().next().is_none(), "guilds appeared");
+			}
+			Ok(())
+		}
+	}
+}
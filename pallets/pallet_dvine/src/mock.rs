@@ -1,6 +1,7 @@
 use crate as pallet_template;
-use frame_support::parameter_types;
+use frame_support::{parameter_types, weights::IdentityFee, PalletId};
 use frame_system as system;
+use pallet_transaction_payment::CurrencyAdapter;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -18,18 +19,22 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	// No base extrinsic weight, so fees are easy to work out.
+	pub BlockWeights: system::limits::BlockWeights = system::limits::BlockWeights::simple_max(1024);
 	pub const SS58Prefix: u8 = 42;
 }
 
 impl system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = ();
+	type BlockWeights = BlockWeights;
 	type BlockLength = ();
 	type DbWeight = ();
 	type Origin = Origin;
@@ -45,7 +50,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +58,70 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: u64 = 1;
+	pub const OperationalFeeMultiplier: u8 = 5;
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type TransactionByteFee = TransactionByteFee;
+	type OperationalFeeMultiplier = OperationalFeeMultiplier;
+	type WeightToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub const SponsorshipEra: u64 = 10;
+	pub const MaxSponsoredCalls: u32 = 4;
+	pub const MaxSponsoredReaders: u32 = 2;
+	pub const GuildPalletId: PalletId = PalletId(*b"dv/guild");
+	pub const GuildDeposit: u64 = 10;
+	pub const GuildProposalDeposit: u64 = 5;
+	pub const MaxGuildMembers: u32 = 4;
+	pub const MaxGuildProposals: u32 = 2;
+	pub const MaxFeaturedWorks: u32 = 2;
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type SponsorshipEra = SponsorshipEra;
+	type MaxSponsoredCalls = MaxSponsoredCalls;
+	type MaxSponsoredReaders = MaxSponsoredReaders;
+	type Currency = Balances;
+	type GuildDeposit = GuildDeposit;
+	type GuildProposalDeposit = GuildProposalDeposit;
+	type PalletId = GuildPalletId;
+	type MaxGuildMembers = MaxGuildMembers;
+	type MaxGuildProposals = MaxGuildProposals;
+	type MaxFeaturedWorks = MaxFeaturedWorks;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: (1..=5).map(|who| (who, 100)).collect() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not deposited in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
@@ -0,0 +1,125 @@
+//! A transaction payment extension that lets sponsors pay for their readers' calls.
+
+use crate::{Call, Config, Pallet};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::IsSubType,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use pallet_transaction_payment::{
+	ChargeTransactionPayment, Config as PaymentConfig, OnChargeTransaction,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{TransactionValidity, TransactionValidityError},
+	DispatchResult, FixedPointOperand,
+};
+
+type TxBalanceOf<T> =
+	<<T as PaymentConfig>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
+
+/// Charges transaction fees like `pallet_transaction_payment::ChargeTransactionPayment`, except
+/// that calls into this pallet covered by the signer's sponsor are paid for by the sponsor.
+///
+/// Sponsors never pay tips: a sponsored call is charged its fee only, whatever tip the reader
+/// signed.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeSponsoredTransactionPayment<T: Config + PaymentConfig>(
+	ChargeTransactionPayment<T>,
+);
+
+impl<T> ChargeSponsoredTransactionPayment<T>
+where
+	T: Config + PaymentConfig,
+	<T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: TxBalanceOf<T>) -> Self {
+		Self(ChargeTransactionPayment::from(tip))
+	}
+
+	/// The sponsor paying for `call` on behalf of `who`, if any.
+	fn sponsor(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::Call,
+	) -> Option<T::AccountId> {
+		call.is_sub_type().and_then(|call| Pallet::<T>::sponsor_for(who, call))
+	}
+
+	/// Charges the fee of a sponsored call, without a tip.
+	fn sponsored() -> ChargeTransactionPayment<T> {
+		ChargeTransactionPayment::from(Zero::zero())
+	}
+}
+
+impl<T: Config + PaymentConfig> sp_std::fmt::Debug for ChargeSponsoredTransactionPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeSponsoredTransactionPayment({:?})", self.0)
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T> SignedExtension for ChargeSponsoredTransactionPayment<T>
+where
+	T: Config + PaymentConfig + Send + Sync,
+	TxBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	<T as frame_system::Config>::Call:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "ChargeSponsoredTransactionPayment";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = <ChargeTransactionPayment<T> as SignedExtension>::Pre;
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match Self::sponsor(who, call) {
+			Some(sponsor) => Self::sponsored().validate(&sponsor, call, info, len),
+			None => self.0.validate(who, call, info, len),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match Self::sponsor(who, call) {
+			Some(sponsor) => {
+				let pre = Self::sponsored().pre_dispatch(&sponsor, call, info, len)?;
+				Pallet::<T>::note_sponsored_call(who);
+				Ok(pre)
+			},
+			None => self.0.pre_dispatch(who, call, info, len),
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result)
+	}
+}
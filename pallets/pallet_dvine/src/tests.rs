@@ -1,20 +1,719 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, Call as DvineCall, ChargeSponsoredTransactionPayment, Error,
+	Event as DvineEvent, GuildAction, SponsoredCall,
+};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageInfoTrait, StorageVersion},
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use sp_runtime::{traits::SignedExtension, Percent};
 
 #[test]
-fn it_works_for_default_value() {
+fn set_allowance_rejects_too_many_calls() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_noop!(
+			TemplateModule::set_allowance(Origin::signed(1), vec![SponsoredCall::FundGuild; 5], 5),
+			Error::<Test>::TooManySponsoredCalls
+		);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn allowances_name_calls_independently_of_call_indices() {
+	let fund = DvineCall::<Test>::fund_guild { guild_id: 0, amount: 10 };
+	assert_eq!(SponsoredCall::of(&fund), Some(SponsoredCall::FundGuild));
+	assert_eq!(SponsoredCall::FundGuild.encode(), vec![1]);
+
+	// Managing sponsorships is never sponsored.
+	assert_eq!(SponsoredCall::of(&DvineCall::<Test>::sponsor { reader: 2 }), None);
+	let set_allowance = DvineCall::<Test>::set_allowance { calls: vec![], per_era: 1 };
+	assert_eq!(SponsoredCall::of(&set_allowance), None);
+}
+
+/// Offer a sponsorship from `sponsor` to `reader`, and accept it.
+fn sponsor(sponsor: u64, reader: u64) {
+	assert_ok!(TemplateModule::sponsor(Origin::signed(sponsor), reader));
+	assert_ok!(TemplateModule::accept_sponsor(Origin::signed(reader), sponsor));
+}
+
+#[test]
+fn sponsor_requires_allowance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::sponsor(Origin::signed(1), 2), Error::<Test>::NoAllowance);
+
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		sponsor(1, 2);
+		assert_eq!(TemplateModule::sponsor_of(2), Some(1));
+		assert_eq!(TemplateModule::sponsored_readers(1).into_inner(), vec![2]);
+	});
+}
+
+#[test]
+fn reader_must_accept_sponsorship() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		assert_noop!(
+			TemplateModule::accept_sponsor(Origin::signed(2), 1),
+			Error::<Test>::NoSponsorOffer
+		);
+
+		assert_ok!(TemplateModule::sponsor(Origin::signed(1), 2));
+		assert_eq!(TemplateModule::sponsor_of(2), None);
+		assert_ok!(TemplateModule::accept_sponsor(Origin::signed(2), 1));
+		assert_eq!(TemplateModule::sponsor_of(2), Some(1));
+
+		// Another sponsor's offer doesn't replace the sponsor until the reader leaves it.
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(3), vec![], 5));
+		assert_ok!(TemplateModule::sponsor(Origin::signed(3), 2));
+		assert_noop!(
+			TemplateModule::accept_sponsor(Origin::signed(2), 3),
+			Error::<Test>::AlreadySponsored
+		);
+		assert_ok!(TemplateModule::leave_sponsor(Origin::signed(2)));
+		assert_ok!(TemplateModule::accept_sponsor(Origin::signed(2), 3));
+		assert_eq!(TemplateModule::sponsor_of(2), Some(3));
+		assert!(TemplateModule::sponsored_readers(1).is_empty());
+	});
+}
+
+#[test]
+fn reader_can_leave_sponsor() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(TemplateModule::leave_sponsor(Origin::signed(2)), Error::<Test>::NotSponsored);
+
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		sponsor(1, 2);
+		TemplateModule::note_sponsored_call(&2);
+
+		assert_ok!(TemplateModule::leave_sponsor(Origin::signed(2)));
+		assert_eq!(TemplateModule::sponsor_of(2), None);
+		assert_eq!(TemplateModule::sponsored_usage(2), (0, 0));
+		assert!(TemplateModule::sponsored_readers(1).is_empty());
+		System::assert_last_event(Event::TemplateModule(DvineEvent::ReaderUnsponsored(1, 2)));
+	});
+}
+
+#[test]
+fn only_sponsor_can_unsponsor() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		sponsor(1, 2);
+
+		assert_noop!(TemplateModule::unsponsor(Origin::signed(3), 2), Error::<Test>::NotSponsor);
+		assert_ok!(TemplateModule::unsponsor(Origin::signed(1), 2));
+		assert_eq!(TemplateModule::sponsor_of(2), None);
+		assert!(TemplateModule::sponsored_readers(1).is_empty());
+
+		// A pending offer can be withdrawn too.
+		assert_ok!(TemplateModule::sponsor(Origin::signed(1), 3));
+		assert_ok!(TemplateModule::unsponsor(Origin::signed(1), 3));
+		assert_noop!(
+			TemplateModule::accept_sponsor(Origin::signed(3), 1),
+			Error::<Test>::NoSponsorOffer
+		);
+	});
+}
+
+#[test]
+fn sponsor_limits_readers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		sponsor(1, 2);
+		sponsor(1, 3);
+
+		assert_ok!(TemplateModule::sponsor(Origin::signed(1), 4));
+		assert_noop!(
+			TemplateModule::accept_sponsor(Origin::signed(4), 1),
+			Error::<Test>::TooManySponsoredReaders
+		);
+	});
+}
+
+#[test]
+fn remove_allowance_ends_sponsorships() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(1), vec![], 5));
+		sponsor(1, 2);
+		sponsor(1, 3);
+		TemplateModule::note_sponsored_call(&2);
+
+		assert_ok!(TemplateModule::remove_allowance(Origin::signed(1)));
+		assert_eq!(TemplateModule::sponsor_of(2), None);
+		assert_eq!(TemplateModule::sponsor_of(3), None);
+		assert_eq!(TemplateModule::sponsored_usage(2), (0, 0));
+		assert!(TemplateModule::sponsored_readers(1).is_empty());
+
+		// The readers are free to accept another sponsor.
+		assert_ok!(TemplateModule::set_allowance(Origin::signed(4), vec![], 5));
+		sponsor(4, 2);
+	});
+}
+
+#[test]
+fn sponsor_pays_only_for_covered_calls() {
+	new_test_ext().execute_with(|| {
+		let covered = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		let uncovered =
+			DvineCall::create_guild { members: vec![], threshold: Percent::from_percent(50) };
+
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			5
+		));
+		assert_eq!(TemplateModule::sponsor_for(&2, &covered), None);
+
+		sponsor(1, 2);
+		assert_eq!(TemplateModule::sponsor_for(&2, &covered), Some(1));
+		assert_eq!(TemplateModule::sponsor_for(&2, &uncovered), None);
+
+		// Removing the allowance stops the sponsorship.
+		assert_ok!(TemplateModule::remove_allowance(Origin::signed(1)));
+		assert_eq!(TemplateModule::sponsor_for(&2, &covered), None);
+	});
+}
+
+#[test]
+fn sponsored_calls_are_rate_limited_per_era() {
+	new_test_ext().execute_with(|| {
+		let call = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			2
+		));
+		sponsor(1, 2);
+
+		System::set_block_number(1);
+		TemplateModule::note_sponsored_call(&2);
+		assert_eq!(TemplateModule::sponsor_for(&2, &call), Some(1));
+		TemplateModule::note_sponsored_call(&2);
+		assert_eq!(TemplateModule::sponsor_for(&2, &call), None);
+
+		// The allowance is restored in the next era.
+		System::set_block_number(10);
+		assert_eq!(TemplateModule::sponsor_for(&2, &call), Some(1));
+		TemplateModule::note_sponsored_call(&2);
+		assert_eq!(TemplateModule::sponsored_usage(2), (1, 1));
+	});
+}
+
+/// Charge `who` for `call` through `ChargeSponsoredTransactionPayment`, as a 10 byte extrinsic of
+/// weight 5. The fee is 15.
+fn charge(who: u64, call: DvineCall<Test>) {
+	charge_with_tip(who, call, 0)
+}
+
+/// Like `charge`, with `tip` on top of the fee.
+fn charge_with_tip(who: u64, call: DvineCall<Test>, tip: u64) {
+	let call = Call::TemplateModule(call);
+	let info = DispatchInfo { weight: 5, ..Default::default() };
+	let pre = ChargeSponsoredTransactionPayment::<Test>::from(tip)
+		.pre_dispatch(&who, &call, &info, 10)
+		.unwrap();
+	assert_ok!(ChargeSponsoredTransactionPayment::<Test>::post_dispatch(
+		pre,
+		&info,
+		&PostDispatchInfo::default(),
+		10,
+		&Ok(())
+	));
+}
+
+#[test]
+fn sponsored_payment_charges_sponsor() {
+	new_test_ext().execute_with(|| {
+		let call = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			2
+		));
+		sponsor(1, 2);
+
+		charge(2, call);
+		assert_eq!(Balances::free_balance(1), 85);
+		assert_eq!(Balances::free_balance(2), 100);
+		assert_eq!(TemplateModule::sponsored_usage(2), (0, 1));
+	});
+}
+
+#[test]
+fn sponsors_never_pay_tips() {
+	new_test_ext().execute_with(|| {
+		let call = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			1
+		));
+		sponsor(1, 2);
+
+		charge_with_tip(2, call.clone(), 50);
+		assert_eq!(Balances::free_balance(1), 85);
+		assert_eq!(Balances::free_balance(2), 100);
+
+		// Once the allowance is used up, the reader pays its own tip.
+		charge_with_tip(2, call, 50);
+		assert_eq!(Balances::free_balance(1), 85);
+		assert_eq!(Balances::free_balance(2), 35);
+	});
+}
+
+#[test]
+fn sponsor_offer_pays_for_accepting_it() {
+	new_test_ext().execute_with(|| {
+		let call = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			1
+		));
+
+		// No offer yet.
+		charge(2, DvineCall::accept_sponsor { sponsor: 1 });
+		assert_eq!(Balances::free_balance(2), 85);
+
+		assert_ok!(TemplateModule::sponsor(Origin::signed(1), 2));
+		charge(2, DvineCall::accept_sponsor { sponsor: 1 });
+		assert_ok!(TemplateModule::accept_sponsor(Origin::signed(2), 1));
+		assert_eq!(Balances::free_balance(1), 85);
+		assert_eq!(Balances::free_balance(2), 85);
+
+		// Accepting did not use up the allowance.
+		assert_eq!(TemplateModule::sponsored_usage(2), (0, 0));
+		assert_eq!(TemplateModule::sponsor_for(&2, &call), Some(1));
+
+		// A full sponsor does not pay for an acceptance that would fail.
+		sponsor(1, 3);
+		assert_ok!(TemplateModule::sponsor(Origin::signed(1), 4));
+		let accept = DvineCall::accept_sponsor { sponsor: 1 };
+		assert_eq!(TemplateModule::sponsor_for(&4, &accept), None);
+	});
+}
+
+#[test]
+fn sponsored_payment_falls_back_to_signer() {
+	new_test_ext().execute_with(|| {
+		let covered = DvineCall::fund_guild { guild_id: 0, amount: 10 };
+		let uncovered =
+			DvineCall::create_guild { members: vec![], threshold: Percent::from_percent(50) };
+		assert_ok!(TemplateModule::set_allowance(
+			Origin::signed(1),
+			vec![SponsoredCall::FundGuild],
+			1
+		));
+
+		// Not sponsored.
+		charge(2, covered.clone());
+		assert_eq!(Balances::free_balance(2), 85);
+
+		// Not covered by the allowance.
+		sponsor(1, 2);
+		charge(2, uncovered);
+		assert_eq!(Balances::free_balance(2), 70);
+
+		// Over the allowance for the era.
+		charge(2, covered.clone());
+		assert_eq!(Balances::free_balance(1), 85);
+		charge(2, covered);
+		assert_eq!(Balances::free_balance(1), 85);
+		assert_eq!(Balances::free_balance(2), 55);
+	});
+}
+
+/// Create a guild founded by 1 with `members` as members, passing proposals at `threshold`.
+fn create_guild_with(members: Vec<u64>, threshold: Percent) -> u32 {
+	let guild_id = TemplateModule::next_guild_id();
+	assert_ok!(TemplateModule::create_guild(Origin::signed(1), members.clone(), threshold));
+	for who in members {
+		assert_ok!(TemplateModule::accept_guild_invite(Origin::signed(who), guild_id));
+	}
+	guild_id
+}
+
+/// Create a guild founded by 1 with 2 and 3 as members, passing proposals at a 60% approval.
+fn create_guild() -> u32 {
+	create_guild_with(vec![2, 3], Percent::from_percent(60))
+}
+
+#[test]
+fn create_guild_invites_members() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let guild_id = TemplateModule::next_guild_id();
+		assert_ok!(TemplateModule::create_guild(
+			Origin::signed(1),
+			vec![3, 2, 1],
+			Percent::from_percent(60)
+		));
+
+		let guild = TemplateModule::guild(guild_id).unwrap();
+		assert_eq!(guild.members.into_inner(), vec![1]);
+		assert_eq!(guild.invited.into_inner(), vec![2, 3]);
+		assert_eq!(TemplateModule::next_guild_id(), guild_id + 1);
+
+		assert_noop!(
+			TemplateModule::accept_guild_invite(Origin::signed(4), guild_id),
+			Error::<Test>::NotGuildInvitee
+		);
+		assert_ok!(TemplateModule::accept_guild_invite(Origin::signed(2), guild_id));
+		let guild = TemplateModule::guild(guild_id).unwrap();
+		assert_eq!(guild.members.into_inner(), vec![1, 2]);
+		assert_eq!(guild.invited.into_inner(), vec![3]);
+	});
+}
+
+#[test]
+fn guild_deposits_are_reserved_until_closed() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild_with(vec![2], Percent::from_percent(100));
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(2),
+			guild_id,
+			GuildAction::Admit(4)
+		));
+		assert_eq!(Balances::reserved_balance(2), 5);
+
+		// The proposal stays open, as the remaining member has not voted on it.
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(2), guild_id));
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_some());
+		assert_eq!(Balances::reserved_balance(2), 5);
+
+		// The last member takes what is left in the treasury.
+		assert_ok!(TemplateModule::fund_guild(Origin::signed(5), guild_id, 30));
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(1), guild_id));
+		assert!(TemplateModule::guild(guild_id).is_none());
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(1), 130);
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildClosed(guild_id)));
+	});
+}
+
+#[test]
+fn decided_guild_proposal_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(2),
+			guild_id,
+			GuildAction::Admit(4)
+		));
+		assert_eq!(Balances::reserved_balance(2), 5);
+
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(3), guild_id, 0, true));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn create_guild_respects_member_limit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::create_guild(
+				Origin::signed(1),
+				vec![2, 3, 4, 5],
+				Percent::from_percent(50)
+			),
+			Error::<Test>::GuildFull
+		);
+	});
+}
+
+#[test]
+fn only_members_can_propose_and_vote() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		let action = GuildAction::Admit(4);
+
+		assert_noop!(
+			TemplateModule::propose_guild_action(Origin::signed(4), guild_id, action.clone()),
+			Error::<Test>::NotGuildMember
+		);
+		assert_ok!(TemplateModule::propose_guild_action(Origin::signed(1), guild_id, action));
+		assert_noop!(
+			TemplateModule::vote_guild_action(Origin::signed(4), guild_id, 0, true),
+			Error::<Test>::NotGuildMember
+		);
+		assert_noop!(
+			TemplateModule::vote_guild_action(Origin::signed(1), guild_id, 0, true),
+			Error::<Test>::DuplicateGuildVote
+		);
 	});
 }
+
+#[test]
+fn guild_spend_passes_at_threshold() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		let treasury = TemplateModule::guild_account_id(guild_id);
+		assert_ok!(TemplateModule::fund_guild(Origin::signed(5), guild_id, 50));
+		assert_eq!(Balances::free_balance(treasury), 50);
+
+		let action = GuildAction::Spend { to: 4, amount: 20 };
+		assert_ok!(TemplateModule::propose_guild_action(Origin::signed(1), guild_id, action));
+
+		// One of three approvals is below the 60% threshold.
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_some());
+		assert_eq!(Balances::free_balance(4), 100);
+
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 0, true));
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_none());
+		assert_eq!(Balances::free_balance(treasury), 30);
+		assert_eq!(Balances::free_balance(4), 120);
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalExecuted(
+			guild_id,
+			0,
+			Ok(())
+		)));
+	});
+}
+
+#[test]
+fn guild_proposal_is_rejected_once_it_cannot_pass() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Admit(4)
+		));
+
+		// Two of three members can still approve the proposal.
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 0, false));
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_some());
+
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(3), guild_id, 0, false));
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_none());
+		assert!(!TemplateModule::guild(guild_id).unwrap().members.contains(&4));
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalRejected(
+			guild_id, 0,
+		)));
+	});
+}
+
+#[test]
+fn guild_limits_open_proposals() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		for who in [4, 5] {
+			assert_ok!(TemplateModule::propose_guild_action(
+				Origin::signed(1),
+				guild_id,
+				GuildAction::Admit(who)
+			));
+		}
+
+		assert_noop!(
+			TemplateModule::propose_guild_action(
+				Origin::signed(1),
+				guild_id,
+				GuildAction::Expel(3)
+			),
+			Error::<Test>::TooManyGuildProposals
+		);
+	});
+}
+
+#[test]
+fn guild_admits_and_expels_members() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Admit(4)
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 0, true));
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().invited.into_inner(), vec![4]);
+		assert_ok!(TemplateModule::accept_guild_invite(Origin::signed(4), guild_id));
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().members.into_inner(), vec![1, 2, 3, 4]);
+
+		// Votes cast by an expelled member no longer count.
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Spend { to: 3, amount: 1 }
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(3), guild_id, 1, true));
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Expel(3)
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 2, true));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(4), guild_id, 2, true));
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().members.into_inner(), vec![1, 2, 4]);
+		assert_eq!(TemplateModule::guild_proposal(guild_id, 1).unwrap().ayes.into_inner(), vec![1]);
+	});
+}
+
+#[test]
+fn removed_members_proposals_are_withdrawn() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(3),
+			guild_id,
+			GuildAction::Spend { to: 3, amount: 1 }
+		));
+		assert_eq!(Balances::reserved_balance(3), 5);
+
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Expel(3)
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 1, true));
+
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_none());
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().open_proposals, 0);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		System::assert_has_event(Event::TemplateModule(DvineEvent::GuildProposalWithdrawn(
+			guild_id, 0,
+		)));
+
+		// Leaving does the same.
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(2),
+			guild_id,
+			GuildAction::Spend { to: 2, amount: 1 }
+		));
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(2), guild_id));
+		assert!(TemplateModule::guild_proposal(guild_id, 2).is_none());
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+/// Have `guild_id`, founded by 1 with 2 and 3 as members, take `action` with the votes of 1 and 2.
+fn pass(guild_id: u32, action: GuildAction<u64, u64>) {
+	let index = TemplateModule::guild_proposal_count(guild_id);
+	assert_ok!(TemplateModule::propose_guild_action(Origin::signed(1), guild_id, action));
+	assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, index, true));
+}
+
+#[test]
+fn guild_features_member_works() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+
+		pass(guild_id, GuildAction::Feature { author: 3, work: [1; 32] });
+		assert_eq!(TemplateModule::featured_works(guild_id).into_inner(), vec![([1; 32], 3)]);
+		System::assert_has_event(Event::TemplateModule(DvineEvent::GuildWorkFeatured(
+			guild_id, [1; 32], 3,
+		)));
+
+		// Only members' works can be featured, each once.
+		pass(guild_id, GuildAction::Feature { author: 4, work: [2; 32] });
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalExecuted(
+			guild_id,
+			1,
+			Err(Error::<Test>::NotGuildMember.into())
+		)));
+		pass(guild_id, GuildAction::Feature { author: 2, work: [1; 32] });
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalExecuted(
+			guild_id,
+			2,
+			Err(Error::<Test>::AlreadyFeatured.into())
+		)));
+
+		pass(guild_id, GuildAction::Feature { author: 2, work: [2; 32] });
+		pass(guild_id, GuildAction::Feature { author: 1, work: [3; 32] });
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalExecuted(
+			guild_id,
+			4,
+			Err(Error::<Test>::TooManyFeaturedWorks.into())
+		)));
+
+		pass(guild_id, GuildAction::Unfeature([1; 32]));
+		assert_eq!(TemplateModule::featured_works(guild_id).into_inner(), vec![([2; 32], 2)]);
+		pass(guild_id, GuildAction::Unfeature([1; 32]));
+		System::assert_last_event(Event::TemplateModule(DvineEvent::GuildProposalExecuted(
+			guild_id,
+			6,
+			Err(Error::<Test>::NotFeatured.into())
+		)));
+	});
+}
+
+#[test]
+fn featured_works_go_with_their_authors() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild();
+		pass(guild_id, GuildAction::Feature { author: 3, work: [1; 32] });
+		pass(guild_id, GuildAction::Feature { author: 1, work: [2; 32] });
+
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(3), guild_id));
+		assert_eq!(TemplateModule::featured_works(guild_id).into_inner(), vec![([2; 32], 1)]);
+		System::assert_has_event(Event::TemplateModule(DvineEvent::GuildWorkUnfeatured(
+			guild_id, [1; 32],
+		)));
+
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(2), guild_id));
+		assert_ok!(TemplateModule::leave_guild(Origin::signed(1), guild_id));
+		assert!(TemplateModule::featured_works(guild_id).is_empty());
+	});
+}
+
+#[test]
+fn expel_decides_open_proposals_again() {
+	new_test_ext().execute_with(|| {
+		let guild_id = create_guild_with(vec![2, 3, 4], Percent::from_percent(60));
+		assert_ok!(TemplateModule::fund_guild(Origin::signed(5), guild_id, 50));
+
+		// Two of four approvals are below the 60% threshold.
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Spend { to: 5, amount: 20 }
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 0, true));
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_some());
+
+		// Two of the three members left are enough.
+		assert_ok!(TemplateModule::propose_guild_action(
+			Origin::signed(1),
+			guild_id,
+			GuildAction::Expel(4)
+		));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(2), guild_id, 1, true));
+		assert_ok!(TemplateModule::vote_guild_action(Origin::signed(3), guild_id, 1, true));
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().members.into_inner(), vec![1, 2, 3]);
+		assert!(TemplateModule::guild_proposal(guild_id, 0).is_none());
+		assert_eq!(TemplateModule::guild(guild_id).unwrap().open_proposals, 0);
+		assert_eq!(Balances::free_balance(5), 70);
+	});
+}
+
+#[test]
+fn migrate_to_v1_drops_template_leftovers() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<TemplateModule>();
+		put_storage_value(b"TemplateModule", b"Something", &[], 42u32);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(get_storage_value::<u32>(b"TemplateModule", b"Something", &[]), None);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+
+		// Running it again changes nothing, not even state written since.
+		put_storage_value(b"TemplateModule", b"Something", &[], 42u32);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(get_storage_value::<u32>(b"TemplateModule", b"Something", &[]), Some(42));
+	});
+}
+
+#[test]
+fn all_storage_is_bounded() {
+	for info in <TemplateModule as StorageInfoTrait>::storage_info() {
+		let name = String::from_utf8_lossy(&info.storage_name);
+		assert!(info.max_size.is_some(), "{} has no max size", name);
+	}
+}
@@ -0,0 +1,277 @@
+//! Provisional weights for pallet_dvine
+//!
+//! These were not produced by the benchmark CLI. The storage accesses follow the benchmarks in
+//! `benchmarking.rs`, but the execution times are estimates that have not been measured. Replace
+//! this file with the output of the following command, run on the reference hardware, before
+//! relying on it. `scripts/benchmark.sh pallet_dvine` runs it and overwrites this file:
+
+// ./target/release/dvine-node
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_dvine
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./pallets/pallet_dvine/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_dvine.
+pub trait WeightInfo {
+	fn set_allowance(c: u32, ) -> Weight;
+	fn remove_allowance(r: u32, ) -> Weight;
+	fn sponsor() -> Weight;
+	fn unsponsor() -> Weight;
+	fn create_guild(m: u32, ) -> Weight;
+	fn fund_guild() -> Weight;
+	fn propose_guild_action(m: u32, p: u32, ) -> Weight;
+	fn vote_guild_action(m: u32, p: u32, ) -> Weight;
+	fn accept_sponsor() -> Weight;
+	fn leave_sponsor() -> Weight;
+	fn accept_guild_invite(m: u32, ) -> Weight;
+	fn leave_guild(m: u32, p: u32, ) -> Weight;
+}
+
+/// Estimated weights for pallet_dvine, pending a benchmark run on the reference hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Dvine Allowances (r:0 w:1)
+	fn set_allowance(c: u32, ) -> Weight {
+		(17_263_000 as Weight)
+			.saturating_add((36_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Allowances (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine Sponsors (r:0 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn remove_allowance(r: u32, ) -> Weight {
+		(23_112_000 as Weight)
+			.saturating_add((1_904_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: Dvine Allowances (r:1 w:0)
+	// Storage: Dvine Sponsors (r:1 w:0)
+	// Storage: Dvine SponsorOffers (r:0 w:1)
+	fn sponsor() -> Weight {
+		(24_915_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn unsponsor() -> Weight {
+		(27_390_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine NextGuildId (r:1 w:1)
+	// Storage: Dvine Guilds (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_guild(m: u32, ) -> Weight {
+		(22_034_000 as Weight)
+			.saturating_add((151_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn fund_guild() -> Weight {
+		(61_298_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposalCount (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn propose_guild_action(m: u32, p: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add((412_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((8_617_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn vote_guild_action(m: u32, p: u32, ) -> Weight {
+		(42_129_000 as Weight)
+			.saturating_add((455_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((8_702_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Dvine SponsorOffers (r:1 w:1)
+	// Storage: Dvine Allowances (r:1 w:0)
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	fn accept_sponsor() -> Weight {
+		(31_547_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn leave_sponsor() -> Weight {
+		(27_018_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	fn accept_guild_invite(m: u32, ) -> Weight {
+		(19_840_000 as Weight)
+			.saturating_add((118_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn leave_guild(m: u32, p: u32, ) -> Weight {
+		(36_905_000 as Weight)
+			.saturating_add((431_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((9_340_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Dvine Allowances (r:0 w:1)
+	fn set_allowance(c: u32, ) -> Weight {
+		(17_263_000 as Weight)
+			.saturating_add((36_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Allowances (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine Sponsors (r:0 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn remove_allowance(r: u32, ) -> Weight {
+		(23_112_000 as Weight)
+			.saturating_add((1_904_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: Dvine Allowances (r:1 w:0)
+	// Storage: Dvine Sponsors (r:1 w:0)
+	// Storage: Dvine SponsorOffers (r:0 w:1)
+	fn sponsor() -> Weight {
+		(24_915_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn unsponsor() -> Weight {
+		(27_390_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine NextGuildId (r:1 w:1)
+	// Storage: Dvine Guilds (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create_guild(m: u32, ) -> Weight {
+		(22_034_000 as Weight)
+			.saturating_add((151_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn fund_guild() -> Weight {
+		(61_298_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposalCount (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn propose_guild_action(m: u32, p: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add((412_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((8_617_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn vote_guild_action(m: u32, p: u32, ) -> Weight {
+		(42_129_000 as Weight)
+			.saturating_add((455_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((8_702_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: Dvine SponsorOffers (r:1 w:1)
+	// Storage: Dvine Allowances (r:1 w:0)
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	fn accept_sponsor() -> Weight {
+		(31_547_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Sponsors (r:1 w:1)
+	// Storage: Dvine SponsoredReaders (r:1 w:1)
+	// Storage: Dvine SponsoredUsage (r:0 w:1)
+	fn leave_sponsor() -> Weight {
+		(27_018_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	fn accept_guild_invite(m: u32, ) -> Weight {
+		(19_840_000 as Weight)
+			.saturating_add((118_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dvine Guilds (r:1 w:1)
+	// Storage: Dvine GuildProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dvine FeaturedWorks (r:1 w:1)
+	fn leave_guild(m: u32, p: u32, ) -> Weight {
+		(36_905_000 as Weight)
+			.saturating_add((431_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((9_340_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+}
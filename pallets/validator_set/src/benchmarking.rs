@@ -0,0 +1,52 @@
+//! Benchmarking setup for pallet-validator-set
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{traits::EnsureOrigin, BoundedVec};
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+/// Fill the set with `n` validators, the last of which is returned.
+fn fill_validators<T: Config>(n: u32) -> T::AccountId {
+	let validators: Vec<T::AccountId> =
+		(0..n.max(1)).map(|i| account("validator", i, SEED)).collect();
+	let last = validators.last().cloned().unwrap();
+	let validators: BoundedVec<_, T::MaxValidators> = validators.try_into().unwrap();
+	Validators::<T>::put(validators);
+	last
+}
+
+benchmarks! {
+	add_validator {
+		let v in 1 .. T::MaxValidators::get();
+		fill_validators::<T>(v - 1);
+		let validator: T::AccountId = account("new", 0, SEED);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(ValidatorSet::<T>::validators().contains(&validator));
+	}
+
+	remove_validator {
+		let v in 1 .. T::MaxValidators::get();
+		let validator = fill_validators::<T>(v);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin, validator.clone())
+	verify {
+		assert!(!ValidatorSet::<T>::validators().contains(&validator));
+	}
+
+	end_permissioned_phase {
+		Permissioned::<T>::put(true);
+		let origin = T::AddRemoveOrigin::successful_origin();
+	}: _<T::Origin>(origin)
+	verify {
+		assert!(!ValidatorSet::<T>::permissioned());
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
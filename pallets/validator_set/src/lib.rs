@@ -0,0 +1,339 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A permissioned validator set for the phase before staking elects validators.
+///
+/// Governance adds and removes validators, and validators reported offline by
+/// `pallet_im_online` are removed automatically. Once the permissioned phase ends, picking
+/// validators is handed over to the `Successor` session manager, usually `pallet_staking`.
+///
+/// `Successor` follows every session from genesis on, so staking eras and exposures exist and
+/// offences are slashed during the permissioned phase too. Only the validators it elects are
+/// ignored until the phase ends.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use crate::WeightInfo;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_im_online::UnresponsivenessOffence;
+	use pallet_session::historical::IdentificationTuple;
+	use sp_runtime::traits::Convert;
+	use sp_staking::{
+		offence::{Offence, OffenceError, ReportOffence},
+		SessionIndex,
+	};
+	use sp_std::vec::Vec;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_session::Config<ValidatorId = <Self as frame_system::Config>::AccountId>
+		+ pallet_session::historical::Config
+	{
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin that can add and remove validators and end the permissioned phase.
+		type AddRemoveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of validators the set never shrinks below.
+		#[pallet::constant]
+		type MinValidators: Get<u32>;
+
+		/// The maximum number of validators in the set.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+
+		/// The session manager that picks validators once the permissioned phase ends.
+		type Successor: pallet_session::historical::SessionManager<
+			Self::ValidatorId,
+			Self::FullIdentification,
+		>;
+
+		/// Where unresponsiveness reports are passed on to, usually `pallet_offences`.
+		type ReportUnresponsiveness: ReportOffence<
+			Self::AccountId,
+			IdentificationTuple<Self>,
+			UnresponsivenessOffence<IdentificationTuple<Self>>,
+		>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	/// The validators of the permissioned phase.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	/// Validators reported offline, to be removed when the next session is planned.
+	#[pallet::storage]
+	#[pallet::getter(fn offline_validators)]
+	pub type OfflineValidators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	/// Whether this pallet still picks the validators, rather than `Successor`.
+	#[pallet::storage]
+	#[pallet::getter(fn permissioned)]
+	pub type Permissioned<T> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The initial validators. The permissioned phase is skipped when this is empty.
+		pub validators: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { validators: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let validators: BoundedVec<T::AccountId, T::MaxValidators> =
+				self.validators.clone().try_into().unwrap_or_else(|_| {
+					panic!(
+						"{} genesis validators, but MaxValidators is {}",
+						self.validators.len(),
+						T::MaxValidators::get()
+					)
+				});
+			<Permissioned<T>>::put(!validators.is_empty());
+			<Validators<T>>::put(validators);
+		}
+	}
+
+	// Pallets use events to inform users when important changes are made.
+	// https://docs.substrate.io/v3/runtime/events-and-errors
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added to the set. [validator]
+		ValidatorAdded(T::AccountId),
+		/// A validator was removed from the set. [validator]
+		ValidatorRemoved(T::AccountId),
+		/// A validator was reported offline and will be removed from the set. [validator]
+		ValidatorOffline(T::AccountId),
+		/// The permissioned phase ended and `Successor` now picks the validators.
+		PermissionedPhaseEnded,
+	}
+
+	// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The validator is already in the set.
+		AlreadyValidator,
+		/// The validator is not in the set.
+		NotValidator,
+		/// The set already has `MaxValidators` validators.
+		TooManyValidators,
+		/// The set cannot shrink below `MinValidators` validators.
+		TooFewValidators,
+		/// The permissioned phase has already ended.
+		NotPermissioned,
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `validator` to the set. It takes part in the session after the next session
+		/// change, once it has set its session keys.
+		#[pallet::weight(T::WeightInfo::add_validator(T::MaxValidators::get()))]
+		pub fn add_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				ensure!(!validators.contains(&validator), Error::<T>::AlreadyValidator);
+				validators.try_push(validator.clone()).map_err(|_| Error::<T>::TooManyValidators)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ValidatorAdded(validator));
+			Ok(())
+		}
+
+		/// Remove `validator` from the set. It leaves in the session after the next session
+		/// change.
+		#[pallet::weight(T::WeightInfo::remove_validator(T::MaxValidators::get()))]
+		pub fn remove_validator(origin: OriginFor<T>, validator: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			<Validators<T>>::try_mutate(|validators| -> DispatchResult {
+				let position = validators
+					.iter()
+					.position(|v| v == &validator)
+					.ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinValidators::get(),
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(position);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ValidatorRemoved(validator));
+			Ok(())
+		}
+
+		/// End the permissioned phase, handing the choice of validators over to `Successor`
+		/// from the next session change on.
+		#[pallet::weight(T::WeightInfo::end_permissioned_phase())]
+		pub fn end_permissioned_phase(origin: OriginFor<T>) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+
+			ensure!(Self::permissioned(), Error::<T>::NotPermissioned);
+			<Permissioned<T>>::put(false);
+			<OfflineValidators<T>>::kill();
+
+			Self::deposit_event(Event::PermissionedPhaseEnded);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove the validators reported offline, without shrinking below `MinValidators`.
+		fn remove_offline_validators() {
+			let offline = <OfflineValidators<T>>::take();
+			if offline.is_empty() {
+				return
+			}
+
+			<Validators<T>>::mutate(|validators| {
+				for validator in offline {
+					if validators.len() as u32 <= T::MinValidators::get() {
+						break
+					}
+					if let Some(position) = validators.iter().position(|v| v == &validator) {
+						validators.remove(position);
+						Self::deposit_event(Event::ValidatorRemoved(validator));
+					}
+				}
+			});
+		}
+
+		/// The validators of the permissioned phase, with their full identification.
+		///
+		/// Validators `Successor` has just `elected` are identified as it identified them, so they
+		/// are exposed as in the era it planned. The others are identified as of the active era.
+		fn identified_validators(
+			elected: Option<Vec<(T::ValidatorId, T::FullIdentification)>>,
+		) -> Vec<(T::ValidatorId, T::FullIdentification)>
+		where
+			T::FullIdentification: Default,
+		{
+			let elected = elected.unwrap_or_default();
+			Self::validators()
+				.into_iter()
+				.map(|validator| {
+					let full = elected
+						.iter()
+						.find(|(elected, _)| elected == &validator)
+						.map(|(_, full)| full.clone())
+						.or_else(|| T::FullIdentificationOf::convert(validator.clone()))
+						.unwrap_or_default();
+					(validator, full)
+				})
+				.collect()
+		}
+	}
+
+	impl<T: Config>
+		pallet_session::historical::SessionManager<T::ValidatorId, T::FullIdentification>
+		for Pallet<T>
+	where
+		T::FullIdentification: Default,
+	{
+		fn new_session(
+			new_index: SessionIndex,
+		) -> Option<Vec<(T::ValidatorId, T::FullIdentification)>> {
+			let elected = T::Successor::new_session(new_index);
+			if !Self::permissioned() {
+				return elected
+			}
+
+			Self::remove_offline_validators();
+			Some(Self::identified_validators(elected))
+		}
+
+		fn new_session_genesis(
+			new_index: SessionIndex,
+		) -> Option<Vec<(T::ValidatorId, T::FullIdentification)>> {
+			let elected = T::Successor::new_session_genesis(new_index);
+			if !Self::permissioned() {
+				return elected
+			}
+
+			Some(Self::identified_validators(elected))
+		}
+
+		fn start_session(start_index: SessionIndex) {
+			T::Successor::start_session(start_index)
+		}
+
+		fn end_session(end_index: SessionIndex) {
+			T::Successor::end_session(end_index)
+		}
+	}
+
+	/// Validators reported offline by `pallet_im_online` during the permissioned phase are removed
+	/// when the next session is planned. Every report is passed on to `ReportUnresponsiveness`.
+	impl<T: Config>
+		ReportOffence<
+			T::AccountId,
+			IdentificationTuple<T>,
+			UnresponsivenessOffence<IdentificationTuple<T>>,
+		> for Pallet<T>
+	{
+		fn report_offence(
+			reporters: Vec<T::AccountId>,
+			offence: UnresponsivenessOffence<IdentificationTuple<T>>,
+		) -> Result<(), OffenceError> {
+			if Self::permissioned() {
+				<OfflineValidators<T>>::mutate(|offline| {
+					for (validator, _) in offence.offenders() {
+						if offline.contains(&validator) {
+							continue
+						}
+						if offline.try_push(validator.clone()).is_ok() {
+							Self::deposit_event(Event::ValidatorOffline(validator));
+						}
+					}
+				});
+			}
+
+			T::ReportUnresponsiveness::report_offence(reporters, offence)
+		}
+
+		fn is_known_offence(
+			offenders: &[IdentificationTuple<T>],
+			time_slot: &SessionIndex,
+		) -> bool {
+			T::ReportUnresponsiveness::is_known_offence(offenders, time_slot)
+		}
+	}
+}
@@ -0,0 +1,138 @@
+use crate::{mock::*, Error};
+use frame_support::{assert_noop, assert_ok, traits::StorageInfoTrait};
+use pallet_im_online::UnresponsivenessOffence;
+use sp_runtime::DispatchError;
+use sp_staking::offence::ReportOffence;
+
+/// Report `offenders` as unresponsive in the current session.
+fn report_offline(offenders: Vec<u64>) {
+	let offence = UnresponsivenessOffence {
+		session_index: Session::current_index(),
+		validator_set_count: Session::validators().len() as u32,
+		offenders: offenders.into_iter().map(|v| (v, v)).collect(),
+	};
+	assert_ok!(ValidatorSet::report_offence(vec![], offence));
+}
+
+#[test]
+fn genesis_validators_are_active() {
+	new_test_ext().execute_with(|| {
+		assert!(ValidatorSet::permissioned());
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+	});
+}
+
+#[test]
+#[should_panic(expected = "5 genesis validators, but MaxValidators is 4")]
+fn genesis_rejects_too_many_validators() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let _ = crate::GenesisConfig::<Test> { validators: vec![1, 2, 3, 4, 5] }
+		.assimilate_storage(&mut t);
+}
+
+#[test]
+fn only_add_remove_origin_manages_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(ValidatorSet::add_validator(Origin::signed(1), 4), DispatchError::BadOrigin);
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::end_permissioned_phase(Origin::signed(1)),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn added_validator_joins_at_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 4),
+			Error::<Test>::AlreadyValidator
+		);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+
+		enact_planned_validators();
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+
+		assert_noop!(
+			ValidatorSet::add_validator(Origin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+	});
+}
+
+#[test]
+fn removed_validator_leaves_at_next_session() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 4),
+			Error::<Test>::NotValidator
+		);
+
+		assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+		assert_noop!(
+			ValidatorSet::remove_validator(Origin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+
+		enact_planned_validators();
+		assert_eq!(Session::validators(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn offline_validators_are_removed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+		enact_planned_validators();
+
+		report_offline(vec![2, 3, 4]);
+		assert_eq!(ValidatorSet::offline_validators().into_inner(), vec![2, 3, 4]);
+
+		// The set never shrinks below `MinValidators`.
+		enact_planned_validators();
+		assert_eq!(Session::validators(), vec![1, 4]);
+		assert!(ValidatorSet::offline_validators().is_empty());
+	});
+}
+
+#[test]
+fn successor_takes_over_after_permissioned_phase() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::end_permissioned_phase(Origin::root()));
+		assert_noop!(
+			ValidatorSet::end_permissioned_phase(Origin::root()),
+			Error::<Test>::NotPermissioned
+		);
+
+		// Offline reports no longer touch the validator set.
+		report_offline(vec![1]);
+		assert!(ValidatorSet::offline_validators().is_empty());
+
+		enact_planned_validators();
+		assert_eq!(Session::validators(), vec![7, 8]);
+	});
+}
+
+#[test]
+fn successor_follows_sessions_while_permissioned() {
+	new_test_ext().execute_with(|| {
+		enact_planned_validators();
+
+		// The successor's validators are ignored, but its eras run from genesis on.
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+		assert_eq!(STARTED_SESSIONS.with(|started| started.borrow().clone()), vec![0, 1, 2]);
+	});
+}
+
+#[test]
+fn all_storage_is_bounded() {
+	for info in <ValidatorSet as StorageInfoTrait>::storage_info() {
+		let name = String::from_utf8_lossy(&info.storage_name);
+		assert!(info.max_size.is_some(), "{} has no max size", name);
+	}
+}
@@ -0,0 +1,84 @@
+//! Provisional weights for pallet_validator_set
+//!
+//! These were not produced by the benchmark CLI. The storage accesses follow the benchmarks in
+//! `benchmarking.rs`, but the execution times are estimates that have not been measured. Replace
+//! this file with the output of the following command, run on the reference hardware, before
+//! relying on it. `scripts/benchmark.sh pallet_validator_set` runs it and overwrites this file:
+
+// ./target/release/dvine-node
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_validator_set
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./pallets/validator_set/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_validator_set.
+pub trait WeightInfo {
+	fn add_validator(v: u32, ) -> Weight;
+	fn remove_validator(v: u32, ) -> Weight;
+	fn end_permissioned_phase() -> Weight;
+}
+
+/// Estimated weights for pallet_validator_set, pending a benchmark run on the reference hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn add_validator(v: u32, ) -> Weight {
+		(15_412_000 as Weight)
+			.saturating_add((84_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn remove_validator(v: u32, ) -> Weight {
+		(16_037_000 as Weight)
+			.saturating_add((97_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Permissioned (r:1 w:1)
+	// Storage: ValidatorSet OfflineValidators (r:0 w:1)
+	fn end_permissioned_phase() -> Weight {
+		(12_218_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn add_validator(v: u32, ) -> Weight {
+		(15_412_000 as Weight)
+			.saturating_add((84_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Validators (r:1 w:1)
+	fn remove_validator(v: u32, ) -> Weight {
+		(16_037_000 as Weight)
+			.saturating_add((97_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ValidatorSet Permissioned (r:1 w:1)
+	// Storage: ValidatorSet OfflineValidators (r:0 w:1)
+	fn end_permissioned_phase() -> Weight {
+		(12_218_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,228 @@
+//! Cross-consensus messaging with the relay chain and sibling parachains.
+//!
+//! DVINE, the native token held in `Balances`, is reserved on this chain: other chains receive it
+//! through `pallet_xcm::reserve_transfer_assets` while the backing stays in their sovereign
+//! account here. Assets created in `pallet_assets` travel the same way, named by the pallet's
+//! instance and their id. Incoming messages buy execution with DVINE.
+//!
+//! The types that decide how assets move are generic over the pallets and locations they work
+//! with, so the XCM simulator tests build their parachain from them too.
+
+use super::{
+	AccountId, AssetId, Assets, Balance, Balances, Call, Event, Origin, ParachainInfo,
+	ParachainSystem, PolkadotXcm, Runtime, XcmpQueue,
+};
+use frame_support::{
+	match_type, parameter_types,
+	traits::{Everything, Nothing, PalletInfoAccess},
+	weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use polkadot_parachain::primitives::Sibling;
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+	AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, AsPrefixedGeneralIndex,
+	ConvertedConcreteAssetId, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds,
+	FungiblesAdapter, IsConcrete, LocationInverter, NativeAsset, ParentAsSuperuser,
+	ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
+	UsingComponents,
+};
+use xcm_executor::{traits::JustTry, XcmExecutor};
+
+parameter_types! {
+	pub const RelayNetwork: NetworkId = NetworkId::Any;
+	pub RelayChainOrigin: Origin = cumulus_pallet_xcm::Origin::Relay.into();
+	pub Ancestry: MultiLocation = Parachain(ParachainInfo::parachain_id().into()).into();
+	/// DVINE as named by extrinsics on this chain.
+	pub const DvineLocation: MultiLocation = MultiLocation::here();
+	/// DVINE as named by other chains, which is how it arrives when it is sent back here.
+	pub SelfReserve: MultiLocation =
+		MultiLocation::new(1, X1(Parachain(ParachainInfo::parachain_id().into())));
+	/// `pallet_assets` as named by extrinsics on this chain.
+	pub AssetsPalletLocation: MultiLocation =
+		PalletInstance(<Assets as PalletInfoAccess>::index() as u8).into();
+	/// `pallet_assets` as named by other chains.
+	pub SelfAssetsPalletLocation: MultiLocation = MultiLocation::new(
+		1,
+		X2(
+			Parachain(ParachainInfo::parachain_id().into()),
+			PalletInstance(<Assets as PalletInfoAccess>::index() as u8),
+		),
+	);
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+}
+
+/// Converts a `MultiLocation` into the local `AccountId` acting on its behalf.
+pub type LocationToAccountId = (
+	// The parent (relay chain) gets the default account.
+	ParentIsDefault<AccountId>,
+	// Sibling parachains get their sovereign account.
+	SiblingParachainConvertsVia<Sibling, AccountId>,
+	// Plain 32-byte accounts on the relay network map to the same local account.
+	AccountId32Aliases<RelayNetwork, AccountId>,
+);
+
+/// Deposits and withdraws DVINE to and from `Currency`. `OwnReserve` names DVINE the way other
+/// chains do.
+pub type LocalAssetTransactorOf<Currency, OwnReserve> = CurrencyAdapter<
+	Currency,
+	(IsConcrete<DvineLocation>, IsConcrete<OwnReserve>),
+	LocationToAccountId,
+	AccountId,
+	(),
+>;
+pub type LocalAssetTransactor = LocalAssetTransactorOf<Balances, SelfReserve>;
+
+/// Maps `PalletInstance(assets) / GeneralIndex(id)` to the `pallet_assets` id it names, with the
+/// pallet named from this chain by `AssetsPallet` and from other chains by `OwnAssetsPallet`.
+pub type AssetIdFromLocationOf<AssetsPallet, OwnAssetsPallet> = (
+	AsPrefixedGeneralIndex<AssetsPallet, AssetId, JustTry>,
+	AsPrefixedGeneralIndex<OwnAssetsPallet, AssetId, JustTry>,
+);
+pub type AssetIdFromLocation =
+	AssetIdFromLocationOf<AssetsPalletLocation, SelfAssetsPalletLocation>;
+
+/// Deposits and withdraws assets held in `Fungibles`. None of them are teleported, so there is
+/// no teleport checking account to keep in sync.
+pub type FungiblesTransactorOf<Fungibles, AssetsPallet, OwnAssetsPallet, CheckAccount> =
+	FungiblesAdapter<
+		Fungibles,
+		ConvertedConcreteAssetId<
+			AssetId,
+			Balance,
+			AssetIdFromLocationOf<AssetsPallet, OwnAssetsPallet>,
+			JustTry,
+		>,
+		LocationToAccountId,
+		AccountId,
+		Nothing,
+		CheckAccount,
+	>;
+pub type FungiblesTransactor = FungiblesTransactorOf<
+	Assets,
+	AssetsPalletLocation,
+	SelfAssetsPalletLocation,
+	CheckingAccount,
+>;
+
+/// Every asset this chain knows how to hold.
+pub type AssetTransactors = (LocalAssetTransactor, FungiblesTransactor);
+
+/// Converts the origin of an incoming `Transact` into a local dispatch origin.
+pub type XcmOriginToTransactDispatchOrigin = (
+	// Sovereign accounts act as signed origins.
+	SovereignSignedViaLocation<LocationToAccountId, Origin>,
+	// The relay chain and sibling parachains keep their native origins for pallets that know them.
+	RelayChainAsNative<RelayChainOrigin, Origin>,
+	SiblingParachainAsNative<cumulus_pallet_xcm::Origin, Origin>,
+	// The relay chain governs this parachain, so it may dispatch as root.
+	ParentAsSuperuser<Origin>,
+	// Accounts on the relay network act as the local account with the same id.
+	SignedAccountId32AsNative<RelayNetwork, Origin>,
+	// Messages sent by `pallet_xcm` keep their XCM origin.
+	pallet_xcm::XcmPassthrough<Origin>,
+);
+
+parameter_types! {
+	/// The weight of a single XCM instruction.
+	pub UnitWeightCost: Weight = 1_000_000_000;
+	pub const MaxInstructions: u32 = 100;
+}
+
+match_type! {
+	pub type ParentOrParentsExecutivePlurality: impl Contains<MultiLocation> = {
+		MultiLocation { parents: 1, interior: Here } |
+		MultiLocation { parents: 1, interior: X1(Plurality { id: BodyId::Executive, .. }) }
+	};
+}
+
+/// Messages must pay for their execution, except those from the relay chain and its executive.
+/// Responses to queries made through `ResponseHandler` and XCM version subscriptions are always
+/// let through.
+pub type BarrierOf<ResponseHandler> = (
+	TakeWeightCredit,
+	AllowTopLevelPaidExecutionFrom<Everything>,
+	AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
+	AllowKnownQueryResponses<ResponseHandler>,
+	AllowSubscriptionsFrom<Everything>,
+);
+pub type Barrier = BarrierOf<PolkadotXcm>;
+
+/// Execution is bought with DVINE held in `Currency`, one unit per unit of weight.
+pub type TraderOf<Currency, OwnReserve> =
+	UsingComponents<IdentityFee<Balance>, OwnReserve, AccountId, Currency, ()>;
+pub type Trader = TraderOf<Balances, SelfReserve>;
+
+/// Chains are trusted as the reserve of the assets they name themselves.
+pub type TrustedReserves = NativeAsset;
+/// No assets are teleported.
+pub type TrustedTeleporters = ();
+/// DVINE and `pallet_assets` assets leave this chain by reserve transfer only.
+pub type XcmReserveTransferFilter = Everything;
+pub type XcmTeleportFilter = Nothing;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type Call = Call;
+	type XcmSender = XcmRouter;
+	type AssetTransactor = AssetTransactors;
+	type OriginConverter = XcmOriginToTransactDispatchOrigin;
+	type IsReserve = TrustedReserves;
+	type IsTeleporter = TrustedTeleporters;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Barrier = Barrier;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+	type Trader = Trader;
+	type ResponseHandler = PolkadotXcm;
+	type AssetTrap = PolkadotXcm;
+	type AssetClaims = PolkadotXcm;
+	type SubscriptionService = PolkadotXcm;
+}
+
+/// Converts a local signed origin into an XCM location, for sending messages.
+pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+
+/// Messages to the relay chain go through UMP, messages to siblings through XCMP.
+pub type XcmRouter = (
+	cumulus_primitives_utility::ParentAsUmp<ParachainSystem, PolkadotXcm>,
+	XcmpQueue,
+);
+
+impl pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmRouter = XcmRouter;
+	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	// Local accounts may send messages, but not execute arbitrary ones.
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = XcmTeleportFilter;
+	type XcmReserveTransferFilter = XcmReserveTransferFilter;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Origin = Origin;
+	type Call = Call;
+
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+impl cumulus_pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+impl cumulus_pallet_xcmp_queue::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type ChannelInfo = ParachainSystem;
+	type VersionWrapper = PolkadotXcm;
+}
+
+impl cumulus_pallet_dmp_queue::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+}
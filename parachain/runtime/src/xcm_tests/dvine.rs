@@ -0,0 +1,206 @@
+//! A parachain carrying the asset side of the Dvine runtime's XCM configuration.
+//!
+//! The simulator cannot drive `cumulus_pallet_parachain_system`, so the queues are replaced by
+//! [`msg_queue`] and the parachain id is read from there. Everything that decides how assets
+//! move (the transactors, the barrier, the trader and the reserve filters) is built from
+//! [`crate::xcm_config`], with this runtime's pallets plugged in.
+
+use super::msg_queue;
+use crate::xcm_config::{
+	BarrierOf, FungiblesTransactorOf, LocalAssetTransactorOf, LocationToAccountId, RelayNetwork,
+	TraderOf, TrustedReserves, TrustedTeleporters, XcmReserveTransferFilter, XcmTeleportFilter,
+};
+pub use crate::{AccountId, AssetId, Balance};
+use frame_support::{
+	construct_runtime, parameter_types,
+	traits::{Everything, Nothing, PalletInfoAccess},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+	EnsureXcmOrigin, FixedWeightBounds, LocationInverter, SignedAccountId32AsNative,
+	SignedToAccountId32, SovereignSignedViaLocation,
+};
+use xcm_executor::XcmExecutor;
+
+/// The weight, and with `IdentityFee` also the DVINE price, of a single XCM instruction.
+pub const UNIT_WEIGHT_COST: Weight = 10;
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = Everything;
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+	type MaxLocks = MaxLocks;
+	type MaxReserves = MaxReserves;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDeposit: Balance = 0;
+	pub const ApprovalDeposit: Balance = 0;
+	pub const AssetsStringLimit: u32 = 50;
+	pub const MetadataDepositBase: Balance = 0;
+	pub const MetadataDepositPerByte: Balance = 0;
+}
+
+impl pallet_assets::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = AssetDeposit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type ApprovalDeposit = ApprovalDeposit;
+	type StringLimit = AssetsStringLimit;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
+	pub SelfReserve: MultiLocation =
+		MultiLocation::new(1, X1(Parachain(MsgQueue::parachain_id().into())));
+	pub AssetsPalletLocation: MultiLocation =
+		PalletInstance(<Assets as PalletInfoAccess>::index() as u8).into();
+	pub SelfAssetsPalletLocation: MultiLocation = MultiLocation::new(
+		1,
+		X2(
+			Parachain(MsgQueue::parachain_id().into()),
+			PalletInstance(<Assets as PalletInfoAccess>::index() as u8),
+		),
+	);
+	pub CheckingAccount: AccountId = PolkadotXcm::check_account();
+	pub const UnitWeightCost: Weight = UNIT_WEIGHT_COST;
+	pub const MaxInstructions: u32 = 100;
+}
+
+pub type LocalAssetTransactor = LocalAssetTransactorOf<Balances, SelfReserve>;
+
+pub type FungiblesTransactor = FungiblesTransactorOf<
+	Assets,
+	AssetsPalletLocation,
+	SelfAssetsPalletLocation,
+	CheckingAccount,
+>;
+
+/// Without `cumulus_pallet_xcm`, the relay chain and siblings have no native origins here.
+pub type XcmOriginToTransactDispatchOrigin = (
+	SovereignSignedViaLocation<LocationToAccountId, Origin>,
+	SignedAccountId32AsNative<RelayNetwork, Origin>,
+	pallet_xcm::XcmPassthrough<Origin>,
+);
+
+pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+
+pub struct XcmConfig;
+impl xcm_executor::Config for XcmConfig {
+	type Call = Call;
+	type XcmSender = XcmRouter;
+	type AssetTransactor = (LocalAssetTransactor, FungiblesTransactor);
+	type OriginConverter = XcmOriginToTransactDispatchOrigin;
+	type IsReserve = TrustedReserves;
+	type IsTeleporter = TrustedTeleporters;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Barrier = BarrierOf<PolkadotXcm>;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+	type Trader = TraderOf<Balances, SelfReserve>;
+	type ResponseHandler = PolkadotXcm;
+	type AssetTrap = PolkadotXcm;
+	type AssetClaims = PolkadotXcm;
+	type SubscriptionService = PolkadotXcm;
+}
+
+impl msg_queue::Config for Runtime {
+	type Event = Event;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+	type Event = Event;
+	type SendXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmRouter = XcmRouter;
+	type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+	type XcmExecuteFilter = Nothing;
+	type XcmExecutor = XcmExecutor<XcmConfig>;
+	type XcmTeleportFilter = XcmTeleportFilter;
+	type XcmReserveTransferFilter = XcmReserveTransferFilter;
+	type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+	type LocationInverter = LocationInverter<Ancestry>;
+	type Origin = Origin;
+	type Call = Call;
+
+	const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+	type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+		MsgQueue: msg_queue::{Pallet, Storage, Event<T>},
+		PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+	}
+);
+
+/// Names a `pallet_assets` asset the way extrinsics on this chain do.
+pub fn local_asset_location(id: AssetId) -> MultiLocation {
+	MultiLocation::new(
+		0,
+		X2(PalletInstance(<Assets as PalletInfoAccess>::index() as u8), GeneralIndex(id.into())),
+	)
+}
@@ -0,0 +1,226 @@
+//! Moves DVINE and `pallet_assets` assets between the Dvine parachain and a sibling on a
+//! simulated network.
+
+mod dvine;
+mod msg_queue;
+mod relay_chain;
+mod sibling;
+
+use frame_support::assert_ok;
+use polkadot_parachain::primitives::Sibling;
+use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+
+pub const DVINE_PARA_ID: u32 = 2000;
+pub const SIBLING_PARA_ID: u32 = 2001;
+
+decl_test_parachain! {
+	pub struct DvinePara {
+		Runtime = dvine::Runtime,
+		XcmpMessageHandler = dvine::MsgQueue,
+		DmpMessageHandler = dvine::MsgQueue,
+		new_ext = dvine_ext(),
+	}
+}
+
+decl_test_parachain! {
+	pub struct SiblingPara {
+		Runtime = sibling::Runtime,
+		XcmpMessageHandler = sibling::MsgQueue,
+		DmpMessageHandler = sibling::MsgQueue,
+		new_ext = sibling_ext(),
+	}
+}
+
+decl_test_relay_chain! {
+	pub struct Relay {
+		Runtime = relay_chain::Runtime,
+		XcmConfig = relay_chain::XcmConfig,
+		new_ext = relay_ext(),
+	}
+}
+
+decl_test_network! {
+	pub struct MockNet {
+		relay_chain = Relay,
+		parachains = vec![
+			(DVINE_PARA_ID, DvinePara),
+			(SIBLING_PARA_ID, SiblingPara),
+		],
+	}
+}
+
+fn dvine_ext() -> sp_io::TestExternalities {
+	use dvine::{MsgQueue, Runtime, System};
+
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, INITIAL_BALANCE)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(DVINE_PARA_ID.into());
+	});
+	ext
+}
+
+fn sibling_ext() -> sp_io::TestExternalities {
+	use sibling::{Assets, MsgQueue, Origin, Runtime, System, DVINE_ASSET_ID};
+
+	let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MsgQueue::set_para_id(SIBLING_PARA_ID.into());
+		assert_ok!(Assets::force_create(Origin::root(), DVINE_ASSET_ID, ALICE, true, 1));
+	});
+	ext
+}
+
+fn relay_ext() -> sp_io::TestExternalities {
+	use relay_chain::{Runtime, System};
+
+	let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The account a sibling parachain controls on the other parachains.
+fn sibling_account(para_id: u32) -> AccountId32 {
+	Sibling::from(para_id).into_account()
+}
+
+fn alice() -> MultiLocation {
+	Junction::AccountId32 { network: Any, id: ALICE.into() }.into()
+}
+
+fn sibling_location() -> MultiLocation {
+	MultiLocation::new(1, X1(Parachain(SIBLING_PARA_ID)))
+}
+
+/// Sends `amount` of the asset at `location` from Alice on the Dvine parachain to Alice on the
+/// sibling.
+fn transfer_to_sibling(location: MultiLocation, amount: u128) {
+	DvinePara::execute_with(|| {
+		let assets: MultiAssets = MultiAsset::from((location, amount)).into();
+		assert_ok!(dvine::PolkadotXcm::reserve_transfer_assets(
+			dvine::Origin::signed(ALICE),
+			Box::new(sibling_location().into()),
+			Box::new(alice().into()),
+			Box::new(assets.into()),
+			0,
+		));
+	});
+}
+
+#[test]
+fn dvine_is_reserve_transferred_to_a_sibling() {
+	MockNet::reset();
+
+	transfer_to_sibling(MultiLocation::here(), 100);
+
+	DvinePara::execute_with(|| {
+		assert_eq!(dvine::Balances::free_balance(&ALICE), INITIAL_BALANCE - 100);
+		assert_eq!(dvine::Balances::free_balance(&sibling_account(SIBLING_PARA_ID)), 100);
+	});
+	SiblingPara::execute_with(|| {
+		assert_eq!(sibling::Assets::balance(sibling::DVINE_ASSET_ID, &ALICE), 100);
+	});
+}
+
+#[test]
+fn dvine_comes_back_from_a_sibling_and_pays_for_execution() {
+	MockNet::reset();
+
+	transfer_to_sibling(MultiLocation::here(), 100);
+
+	// The message arriving on the Dvine parachain is `WithdrawAsset`, `ClearOrigin`,
+	// `BuyExecution` and `DepositAsset`.
+	let fee = 4 * dvine::UNIT_WEIGHT_COST as u128;
+	SiblingPara::execute_with(|| {
+		let dvine_location = MultiLocation::new(1, X1(Parachain(DVINE_PARA_ID)));
+		let asset = MultiAsset::from((dvine_location.clone(), 100u128));
+		let message = Xcm(vec![
+			WithdrawAsset(asset.clone().into()),
+			InitiateReserveWithdraw {
+				assets: All.into(),
+				reserve: dvine_location,
+				xcm: Xcm(vec![
+					BuyExecution {
+						fees: asset,
+						weight_limit: Limited(4 * dvine::UNIT_WEIGHT_COST),
+					},
+					DepositAsset { assets: All.into(), max_assets: 1, beneficiary: alice() },
+				]),
+			},
+		]);
+		assert_ok!(sibling::PolkadotXcm::execute(
+			sibling::Origin::signed(ALICE),
+			Box::new(VersionedXcm::from(message)),
+			1_000_000,
+		));
+		assert_eq!(sibling::Assets::balance(sibling::DVINE_ASSET_ID, &ALICE), 0);
+	});
+
+	DvinePara::execute_with(|| {
+		assert_eq!(dvine::Balances::free_balance(&ALICE), INITIAL_BALANCE - fee);
+		assert_eq!(dvine::Balances::free_balance(&sibling_account(SIBLING_PARA_ID)), 0);
+	});
+}
+
+#[test]
+fn local_assets_are_reserve_transferred_to_a_sibling() {
+	MockNet::reset();
+
+	let asset_id = 7;
+	DvinePara::execute_with(|| {
+		assert_ok!(dvine::Assets::force_create(dvine::Origin::root(), asset_id, ALICE, true, 1));
+		assert_ok!(dvine::Assets::mint(dvine::Origin::signed(ALICE), asset_id, ALICE, 1_000));
+	});
+	SiblingPara::execute_with(|| {
+		assert_ok!(sibling::Assets::force_create(
+			sibling::Origin::root(),
+			sibling::dvine_asset_id(asset_id),
+			ALICE,
+			true,
+			1,
+		));
+	});
+
+	transfer_to_sibling(dvine::local_asset_location(asset_id), 100);
+
+	DvinePara::execute_with(|| {
+		assert_eq!(dvine::Assets::balance(asset_id, &ALICE), 900);
+		assert_eq!(dvine::Assets::balance(asset_id, &sibling_account(SIBLING_PARA_ID)), 100);
+	});
+	SiblingPara::execute_with(|| {
+		assert_eq!(sibling::Assets::balance(sibling::dvine_asset_id(asset_id), &ALICE), 100);
+	});
+}
+
+#[test]
+fn dvine_cannot_be_teleported() {
+	MockNet::reset();
+
+	DvinePara::execute_with(|| {
+		let assets: MultiAssets = MultiAsset::from((MultiLocation::here(), 100u128)).into();
+		assert!(dvine::PolkadotXcm::teleport_assets(
+			dvine::Origin::signed(ALICE),
+			Box::new(sibling_location().into()),
+			Box::new(alice().into()),
+			Box::new(assets.into()),
+			0,
+		)
+		.is_err());
+		assert_eq!(dvine::Balances::free_balance(&ALICE), INITIAL_BALANCE);
+	});
+}
@@ -0,0 +1,99 @@
+//! The functions ink! contracts can call into `pallet_dvine` through `seal_call_chain_extension`.
+
+use crate::{AccountId, Runtime};
+use codec::Encode;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_dvine::GuildId;
+use sp_runtime::DispatchError;
+
+/// `fn version() -> u32`: the [`ABI_VERSION`] of this extension.
+pub const VERSION: u32 = 0;
+/// `fn guild(id: GuildId) -> Option<(Vec<AccountId>, Percent, u32)>`: the members, threshold and
+/// open proposal count of a guild.
+pub const GUILD: u32 = 1;
+/// `fn guild_account(id: GuildId) -> AccountId`: the account holding a guild's treasury, which
+/// contracts can pay proceeds into.
+pub const GUILD_ACCOUNT: u32 = 2;
+/// `fn sponsor_of(reader: AccountId) -> Option<AccountId>`: the sponsor paying a reader's fees.
+pub const SPONSOR_OF: u32 = 3;
+/// `fn guild_details(id: GuildId) -> Option<Guild>`: the founder, deposit, members, pending
+/// invitations, threshold and open proposal count of a guild.
+pub const GUILD_DETAILS: u32 = 4;
+/// `fn featured_works(id: GuildId) -> Vec<(WorkId, AccountId)>`: the works a guild features, with
+/// their authors.
+pub const FEATURED_WORKS: u32 = 5;
+
+/// The version of the functions above.
+///
+/// Functions are only ever added. The encoding of a function's result never changes once it has
+/// shipped, so a new layout gets a new function id instead.
+///
+/// - 1: `GUILD`, `GUILD_ACCOUNT` and `SPONSOR_OF`.
+/// - 2: `VERSION`, `GUILD_DETAILS` for the guild founder, deposit and invitations, and
+///   `FEATURED_WORKS`.
+pub const ABI_VERSION: u32 = 2;
+
+/// Reads `pallet_dvine` state on behalf of contracts, so they don't have to mirror it.
+///
+/// Every function takes its SCALE-encoded arguments in the input buffer and writes its
+/// SCALE-encoded result to the output buffer. Writing the result is charged per byte, like a
+/// contract returning data.
+pub struct DvineExtension;
+
+impl ChainExtension<Runtime> for DvineExtension {
+	fn call<E>(func_id: u32, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let output = match func_id {
+			VERSION => ABI_VERSION.encode(),
+			GUILD => {
+				env.charge_weight(read_weight())?;
+				let id: GuildId = env.read_as()?;
+				pallet_dvine::Pallet::<Runtime>::guild(id)
+					.map(|guild| (guild.members, guild.threshold, guild.open_proposals))
+					.encode()
+			},
+			GUILD_ACCOUNT => {
+				env.charge_weight(read_weight())?;
+				let id: GuildId = env.read_as()?;
+				pallet_dvine::Pallet::<Runtime>::guild_account_id(id).encode()
+			},
+			SPONSOR_OF => {
+				env.charge_weight(read_weight())?;
+				let reader: AccountId = env.read_as()?;
+				pallet_dvine::Pallet::<Runtime>::sponsor_of(reader).encode()
+			},
+			GUILD_DETAILS => {
+				env.charge_weight(read_weight())?;
+				let id: GuildId = env.read_as()?;
+				pallet_dvine::Pallet::<Runtime>::guild(id).encode()
+			},
+			FEATURED_WORKS => {
+				env.charge_weight(read_weight())?;
+				let id: GuildId = env.read_as()?;
+				pallet_dvine::Pallet::<Runtime>::featured_works(id).encode()
+			},
+			_ => return Err(DispatchError::Other("Unknown chain extension function")),
+		};
+		env.write(&output, false, Some(output_byte_weight()))?;
+
+		Ok(RetVal::Converging(0))
+	}
+}
+
+/// The weight of a single storage read. Every function is charged at least this much, even
+/// `GUILD_ACCOUNT`, which only derives an account id.
+fn read_weight() -> Weight {
+	<Runtime as frame_system::Config>::DbWeight::get().reads(1)
+}
+
+/// The weight of writing one byte of a result, the same as a contract returning one byte.
+fn output_byte_weight() -> Weight {
+	<Runtime as pallet_contracts::Config>::Schedule::get().host_fn_weights.return_per_byte
+}
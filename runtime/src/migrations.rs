@@ -0,0 +1,49 @@
+//! Runtime upgrade hooks that are not owned by a single pallet.
+
+use crate::{Runtime, VERSION};
+use frame_support::{
+	storage::migration::{have_storage_value, remove_storage_prefix},
+	traits::{Get, OnRuntimeUpgrade},
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// The migrations run by `Executive` on the next runtime upgrade, in order.
+pub type Migrations = (
+	pallet_dvine::migrations::v1::MigrateToV1<Runtime>,
+	RetireSudo,
+);
+
+/// The spec version that removed `pallet_sudo` from the runtime.
+pub const RETIRE_SUDO_SPEC_VERSION: u32 = 102;
+
+/// Removes what `pallet_sudo` left in storage when the runtime dropped it.
+///
+/// Only the upgrade to [`RETIRE_SUDO_SPEC_VERSION`] does anything, and only if the key is still
+/// there, so this can stay in [`Migrations`] until the next release without touching later
+/// upgrades. Chains started from this runtime never had a sudo key: root calls are dispatched by
+/// `Democracy` referenda, which the council tables, and the admin origins that accept root also
+/// accept council majorities.
+pub struct RetireSudo;
+
+impl OnRuntimeUpgrade for RetireSudo {
+	fn on_runtime_upgrade() -> Weight {
+		if VERSION.spec_version != RETIRE_SUDO_SPEC_VERSION {
+			return 0
+		}
+		if !have_storage_value(b"Sudo", b"Key", &[]) {
+			return RocksDbWeight::get().reads(1)
+		}
+
+		remove_storage_prefix(b"Sudo", b"Key", &[]);
+		RocksDbWeight::get().reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(
+			!have_storage_value(b"Sudo", b"Key", &[]),
+			"the sudo key is still set"
+		);
+		Ok(())
+	}
+}
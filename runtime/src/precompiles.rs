@@ -0,0 +1,204 @@
+//! The precompiled contracts available to the EVM.
+
+use crate::{AccountId, Call, Runtime};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo},
+	traits::Get,
+	weights::Weight,
+};
+use pallet_dvine::GuildId;
+use pallet_evm::{
+	AddressMapping, Context, ExitError, ExitSucceed, GasWeightMapping, Precompile,
+	PrecompileFailure, PrecompileOutput, PrecompileResult, PrecompileSet,
+};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use sp_core::{H160, U256};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The Ethereum precompiles at their usual addresses, followed by the ones Frontier adds from
+/// `0x400` and [`DvinePrecompile`] at `0x800`.
+pub struct DvinePrecompiles<R>(PhantomData<R>);
+
+impl<R> DvinePrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// Every address with a precompile behind it.
+	pub fn used_addresses() -> Vec<H160> {
+		vec![1, 2, 3, 4, 5, 1024, 1025, DVINE].into_iter().map(address).collect()
+	}
+}
+
+impl<R> Default for DvinePrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<R> PrecompileSet for DvinePrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		match address {
+			// Ethereum precompiles.
+			a if a == self::address(1) =>
+				Some(ECRecover::execute(input, target_gas, context, is_static)),
+			a if a == self::address(2) =>
+				Some(Sha256::execute(input, target_gas, context, is_static)),
+			a if a == self::address(3) =>
+				Some(Ripemd160::execute(input, target_gas, context, is_static)),
+			a if a == self::address(4) =>
+				Some(Identity::execute(input, target_gas, context, is_static)),
+			a if a == self::address(5) =>
+				Some(Modexp::execute(input, target_gas, context, is_static)),
+			// Frontier precompiles that Ethereum doesn't have.
+			a if a == self::address(1024) =>
+				Some(Sha3FIPS256::execute(input, target_gas, context, is_static)),
+			a if a == self::address(1025) =>
+				Some(ECRecoverPublicKey::execute(input, target_gas, context, is_static)),
+			// Dvine precompiles.
+			a if a == self::address(DVINE) =>
+				Some(DvinePrecompile::execute(input, target_gas, context, is_static)),
+			_ => None,
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address)
+	}
+}
+
+fn address(index: u64) -> H160 {
+	H160::from_low_u64_be(index)
+}
+
+/// The index of the address [`DvinePrecompile`] lives at.
+pub const DVINE: u64 = 2048;
+
+/// `fundGuild(uint32 guildId, uint256 amount)`: pay `amount` from the caller into the treasury of
+/// a guild, like `pallet_dvine::fund_guild`.
+pub const FUND_GUILD: [u8; 4] = [0xf9, 0x66, 0xea, 0xae];
+/// `guildAccount(uint32 guildId) returns (bytes32)`: the account holding a guild's treasury.
+pub const GUILD_ACCOUNT: [u8; 4] = [0xcf, 0x3c, 0xf2, 0x43];
+
+/// Lets Ethereum wallets and Solidity contracts tip guilds in `pallet_dvine`.
+///
+/// Calls are ABI-encoded and dispatched as the account the caller's address maps to, so a
+/// MetaMask user pays a guild with a plain transaction to this address. `pallet_dvine` has no
+/// edition purchases yet, so there is no call for them here either.
+///
+/// Only direct calls are accepted. A contract reaching this code with `DELEGATECALL` would
+/// otherwise dispatch as whoever called that contract, and value sent along would be stuck at
+/// this address.
+pub struct DvinePrecompile;
+
+impl Precompile for DvinePrecompile {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		if input.len() < 4 {
+			return Err(error("Input has no function selector"))
+		}
+		if context.address != address(DVINE) {
+			return Err(error("Cannot be called with DELEGATECALL or CALLCODE"))
+		}
+		if !context.apparent_value.is_zero() {
+			return Err(error("Function is not payable"))
+		}
+		let (selector, args) = input.split_at(4);
+
+		match selector {
+			s if s == FUND_GUILD => {
+				if is_static {
+					return Err(error("fundGuild cannot be called statically"))
+				}
+				let guild_id = read_guild_id(args, 0)?;
+				let amount = u128::try_from(read_word(args, 1)?)
+					.map_err(|_| error("Amount does not fit a balance"))?;
+				let who = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(
+					context.caller,
+				);
+				let call = pallet_dvine::Call::fund_guild { guild_id, amount };
+				dispatch(who, call.into(), target_gas)
+			},
+			s if s == GUILD_ACCOUNT => {
+				let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+				let cost = charge(weight, target_gas)?;
+				let guild_id = read_guild_id(args, 0)?;
+				let account = pallet_dvine::Pallet::<Runtime>::guild_account_id(guild_id);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: AsRef::<[u8]>::as_ref(&account).to_vec(),
+					logs: vec![],
+				})
+			},
+			_ => Err(error("Unknown function selector")),
+		}
+	}
+}
+
+/// Dispatch `call` as `who`, charging the gas of its weight.
+fn dispatch(who: AccountId, call: Call, target_gas: Option<u64>) -> PrecompileResult {
+	let info = call.get_dispatch_info();
+	charge(info.weight, target_gas)?;
+
+	let post_info = call
+		.dispatch(Some(who).into())
+		.map_err(|e| error(<&'static str>::from(e.error)))?;
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Stopped,
+		cost: gas(post_info.actual_weight.unwrap_or(info.weight)),
+		output: vec![],
+		logs: vec![],
+	})
+}
+
+/// The gas `weight` costs, or an out of gas error if it is more than `target_gas`.
+fn charge(weight: Weight, target_gas: Option<u64>) -> Result<u64, PrecompileFailure> {
+	let cost = gas(weight);
+	match target_gas {
+		Some(target_gas) if cost > target_gas =>
+			Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
+		_ => Ok(cost),
+	}
+}
+
+fn gas(weight: Weight) -> u64 {
+	<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
+/// The 32-byte ABI word at `index` in `args`.
+fn read_word(args: &[u8], index: usize) -> Result<U256, PrecompileFailure> {
+	args.get(index * 32..(index + 1) * 32)
+		.map(U256::from_big_endian)
+		.ok_or_else(|| error("Input is too short"))
+}
+
+fn read_guild_id(args: &[u8], index: usize) -> Result<GuildId, PrecompileFailure> {
+	GuildId::try_from(read_word(args, index)?).map_err(|_| error("Guild id out of range"))
+}
+
+fn error(message: &'static str) -> PrecompileFailure {
+	PrecompileFailure::Error { exit_status: ExitError::Other(message.into()) }
+}
@@ -0,0 +1,250 @@
+use super::*;
+use frame_support::{
+	assert_ok,
+	storage::migration::{have_storage_value, put_storage_value},
+	traits::{EnsureOrigin, Hooks, OnRuntimeUpgrade},
+};
+use sp_consensus_babe::{
+	digests::{PreDigest, SecondaryPlainPreDigest},
+	Slot, BABE_ENGINE_ID,
+};
+use crate::precompiles::{DvinePrecompile, FUND_GUILD, GUILD_ACCOUNT};
+use pallet_evm::{AddressMapping, Context, Precompile};
+use sp_core::{ed25519, sr25519, Pair};
+use sp_runtime::{BuildStorage, DigestItem, Percent};
+
+/// What each validator bonds at genesis.
+const BOND: Balance = 100 * UNIT;
+
+/// Build a runtime with validators 1 and 2, whose session keys are derived from their index.
+/// Each validator is its own controller and has `BOND` staked. The GRANDPA keys are real ones,
+/// seeded with the validator's account, so that the validators can sign votes.
+fn new_test_ext() -> sp_io::TestExternalities {
+	let validators: Vec<AccountId> = (1..=2u8).map(|i| AccountId::from([i; 32])).collect();
+	let keys = validators
+		.iter()
+		.zip(1..=2u8)
+		.map(|(validator, i)| {
+			let keys = opaque::SessionKeys {
+				babe: sr25519::Public::from_raw([i; 32]).into(),
+				grandpa: grandpa_pair(validator).public().into(),
+				im_online: sr25519::Public::from_raw([i; 32]).into(),
+			};
+			(validator.clone(), validator.clone(), keys)
+		})
+		.collect();
+
+	let t = GenesisConfig {
+		balances: BalancesConfig {
+			balances: validators.iter().map(|validator| (validator.clone(), 2 * BOND)).collect(),
+		},
+		babe: BabeConfig { authorities: vec![], epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG) },
+		staking: StakingConfig {
+			validator_count: 2,
+			stakers: validators
+				.iter()
+				.map(|v| (v.clone(), v.clone(), BOND, StakerStatus::Validator))
+				.collect(),
+			..Default::default()
+		},
+		validator_set: ValidatorSetConfig { validators },
+		session: SessionConfig { keys },
+		..Default::default()
+	}
+	.build_storage()
+	.unwrap();
+	sp_io::TestExternalities::new(t)
+}
+
+/// Start block 1 with a secondary BABE pre-digest from the authority at `authority_index`.
+fn author_block_by(authority_index: u32) {
+	let pre_digest =
+		PreDigest::SecondaryPlain(SecondaryPlainPreDigest { authority_index, slot: Slot::from(1) });
+	System::initialize(&1, &Default::default(), &Default::default());
+	System::deposit_log(DigestItem::PreRuntime(BABE_ENGINE_ID, pre_digest.encode()));
+	Babe::on_initialize(1);
+}
+
+#[test]
+fn enabled_authority_can_author() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Babe::authorities().len(), 2);
+		report_equivocation(AccountId::from([2; 32]));
+
+		author_block_by(0);
+		assert_eq!(Babe::current_slot(), Slot::from(1));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Validator with index 1 is disabled")]
+fn disabled_authority_block_is_rejected() {
+	new_test_ext().execute_with(|| {
+		report_equivocation(AccountId::from([2; 32]));
+
+		author_block_by(1);
+	});
+}
+
+/// Rotate sessions until `era` is active, with every validator marked online in each session.
+fn start_era(era: pallet_staking::EraIndex) {
+	while Staking::active_era().unwrap().index < era {
+		for validator in Session::validators() {
+			<ImOnline as pallet_authorship::EventHandler<_, _>>::note_author(validator);
+		}
+		Session::rotate_session();
+	}
+}
+
+/// The GRANDPA key of `validator`.
+fn grandpa_pair(validator: &AccountId) -> ed25519::Pair {
+	ed25519::Pair::from_seed(validator.as_ref())
+}
+
+/// Have `offender` prevote for two blocks in the same round, and report it the way a node would:
+/// unsigned, with a proof that the key belonged to `offender` in the current session.
+fn report_equivocation(offender: AccountId) {
+	let pair = grandpa_pair(&offender);
+	let set_id = Grandpa::current_set_id();
+	let prevote = |target_hash| {
+		let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+		let message = finality_grandpa::Message::Prevote(prevote.clone());
+		let payload = fg_primitives::localized_payload(1, set_id, &message);
+		(prevote, pair.sign(&payload).into())
+	};
+	let proof = fg_primitives::EquivocationProof::new(
+		set_id,
+		fg_primitives::Equivocation::Prevote(finality_grandpa::Equivocation {
+			round_number: 1,
+			identity: pair.public().into(),
+			first: prevote(Hash::repeat_byte(1)),
+			second: prevote(Hash::repeat_byte(2)),
+		}),
+	);
+	let key_owner_proof = Historical::prove((fg_primitives::KEY_TYPE, pair.public())).unwrap();
+
+	assert_ok!(Grandpa::report_equivocation_unsigned(
+		Origin::none(),
+		Box::new(proof),
+		key_owner_proof,
+	));
+}
+
+#[test]
+fn equivocation_disables_and_later_slashes_the_offender() {
+	new_test_ext().execute_with(|| {
+		let (alice, bob) = (AccountId::from([1; 32]), AccountId::from([2; 32]));
+		author_block_by(1);
+		assert_eq!(Staking::ledger(&alice).unwrap().active, BOND);
+
+		report_equivocation(alice.clone());
+		assert_eq!(Session::disabled_validators(), vec![0]);
+
+		// The slash waits for governance to cancel it.
+		start_era(SlashDeferDuration::get());
+		assert_eq!(Staking::ledger(&alice).unwrap().active, BOND);
+
+		// One of two validators equivocating loses its whole bond.
+		start_era(SlashDeferDuration::get() + 1);
+		assert_eq!(Staking::ledger(&alice).unwrap().active, 0);
+		assert_eq!(Balances::free_balance(&alice), BOND);
+		assert_eq!(Staking::ledger(&bob).unwrap().active, BOND);
+	});
+}
+
+#[test]
+fn retire_sudo_hands_root_to_council() {
+	new_test_ext().execute_with(|| {
+		// What a chain started with sudo still holds.
+		put_storage_value(b"Sudo", b"Key", &[], AccountId::from([1; 32]));
+		pallet_collective::Members::<Runtime, CouncilCollective>::put(vec![
+			AccountId::from([1; 32]),
+			AccountId::from([2; 32]),
+		]);
+
+		assert_eq!(VERSION.spec_version, migrations::RETIRE_SUDO_SPEC_VERSION);
+		migrations::Migrations::on_runtime_upgrade();
+		assert!(!have_storage_value(b"Sudo", b"Key", &[]));
+
+		// Running again does nothing.
+		assert_eq!(
+			migrations::RetireSudo::on_runtime_upgrade(),
+			<Runtime as frame_system::Config>::DbWeight::get().reads(1)
+		);
+
+		// The council can table root referenda and take the admin actions directly.
+		let council: Origin =
+			pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(2, 2).into();
+		assert!(<Runtime as pallet_democracy::Config>::ExternalMajorityOrigin::ensure_origin(
+			council.clone()
+		)
+		.is_ok());
+		assert!(
+			<Runtime as pallet_validator_set::Config>::AddRemoveOrigin::ensure_origin(council)
+				.is_ok()
+		);
+	});
+}
+
+/// ABI-encode a call to the function with `selector`, taking `args`.
+#[test]
+fn evm_fees_are_shared_with_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let treasury = Treasury::account_id();
+		let before = Balances::free_balance(&treasury);
+		DealWithFees::on_unbalanced(Balances::issue(100 * UNIT));
+		assert_eq!(Balances::free_balance(&treasury), before + 20 * UNIT);
+	});
+}
+
+fn abi_call(selector: [u8; 4], args: &[U256]) -> Vec<u8> {
+	let mut input = selector.to_vec();
+	for arg in args {
+		let mut word = [0; 32];
+		arg.to_big_endian(&mut word);
+		input.extend_from_slice(&word);
+	}
+	input
+}
+
+#[test]
+fn dvine_precompile_funds_guilds() {
+	new_test_ext().execute_with(|| {
+		let founder = AccountId::from([1; 32]);
+		assert_ok!(Dvine::create_guild(Origin::signed(founder), vec![], Percent::from_percent(50)));
+		let guild_account = Dvine::guild_account_id(0);
+
+		let caller = H160::repeat_byte(7);
+		let payer = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(caller);
+		let _ = Balances::deposit_creating(&payer, 10 * UNIT);
+		let context =
+			Context { address: H160::from_low_u64_be(2048), caller, apparent_value: U256::zero() };
+
+		let input = abi_call(GUILD_ACCOUNT, &[0u32.into()]);
+		let output = DvinePrecompile::execute(&input, None, &context, true).unwrap().output;
+		assert_eq!(output, AsRef::<[u8]>::as_ref(&guild_account).to_vec());
+
+		let input = abi_call(FUND_GUILD, &[0u32.into(), UNIT.into()]);
+		assert!(DvinePrecompile::execute(&input, None, &context, true).is_err());
+		assert!(DvinePrecompile::execute(&input, Some(0), &context, false).is_err());
+		assert!(DvinePrecompile::execute(&input, None, &context, false).is_ok());
+		assert_eq!(Balances::free_balance(&guild_account), UNIT);
+		assert_eq!(Balances::free_balance(&payer), 9 * UNIT);
+
+		// Guilds that don't exist can't be funded.
+		let input = abi_call(FUND_GUILD, &[1u32.into(), UNIT.into()]);
+		assert!(DvinePrecompile::execute(&input, None, &context, false).is_err());
+
+		// A contract delegating to the precompile can't spend its own callers' funds.
+		let input = abi_call(FUND_GUILD, &[0u32.into(), UNIT.into()]);
+		let delegated = Context { address: H160::repeat_byte(9), ..context.clone() };
+		assert!(DvinePrecompile::execute(&input, None, &delegated, false).is_err());
+
+		// Value sent along would be stuck at the precompile.
+		let paying = Context { apparent_value: UNIT.into(), ..context };
+		assert!(DvinePrecompile::execute(&input, None, &paying, false).is_err());
+
+		assert_eq!(Balances::free_balance(&guild_account), UNIT);
+		assert_eq!(Balances::free_balance(&payer), 9 * UNIT);
+	});
+}
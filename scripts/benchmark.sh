@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+set -e
+
+# Regenerates the weights of the pallets in this repository from their benchmarks. Run it on the
+# reference hardware and commit the files it writes unedited, so their headers record how they
+# were generated. Pass pallet names to regenerate only those, e.g. `benchmark.sh pallet_dvine`.
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+NODE=./target/release/dvine-node
+TEMPLATE=./.maintain/frame-weight-template.hbs
+
+declare -A OUTPUTS=(
+	[pallet_dvine]=./pallets/pallet_dvine/src/weights.rs
+	[pallet_validator_set]=./pallets/validator_set/src/weights.rs
+)
+
+PALLETS=${@:-${!OUTPUTS[@]}}
+
+echo "*** Building $NODE with runtime benchmarks ***"
+cargo build --release --features runtime-benchmarks --manifest-path node/Cargo.toml
+
+for PALLET in $PALLETS; do
+	echo "*** Benchmarking $PALLET ***"
+	$NODE benchmark \
+		--chain=dev \
+		--steps=50 \
+		--repeat=20 \
+		--pallet=$PALLET \
+		--extrinsic='*' \
+		--execution=wasm \
+		--wasm-execution=compiled \
+		--heap-pages=4096 \
+		--output=${OUTPUTS[$PALLET]} \
+		--template=$TEMPLATE
+done
@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+set -e
+
+# Starts the `local` chain with Alice and Bob as validators, each in its own base path under
+# /tmp/dvine-local. With `--equivocate`, a second node also runs with Alice's keys. The two
+# Alice nodes sign conflicting GRANDPA votes, so Bob reports an equivocation. The offence shows
+# up as an `offences.Offence` event and Alice is disabled. Her slash is deferred for 27 eras and
+# only then shows up as a `staking.Slashed` event.
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+NODE=./target/release/dvine-node
+BASE=/tmp/dvine-local
+# The peer id of the node key 0x..01.
+ALICE_PEER=12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp
+
+echo "*** Starting a local testnet in $BASE ***"
+
+rm -rf $BASE
+trap 'kill $(jobs -p) 2>/dev/null' EXIT
+
+$NODE --chain local --alice --base-path $BASE/alice --port 30333 --ws-port 9944 \
+	--rpc-port 9933 --validator \
+	--node-key 0000000000000000000000000000000000000000000000000000000000000001 \
+	> $BASE-alice.log 2>&1 &
+
+BOOTNODE=/ip4/127.0.0.1/tcp/30333/p2p/$ALICE_PEER
+
+$NODE --chain local --bob --base-path $BASE/bob --port 30334 --ws-port 9945 \
+	--rpc-port 9934 --validator --bootnodes $BOOTNODE \
+	> $BASE-bob.log 2>&1 &
+
+if [ "$1" == "--equivocate" ]; then
+	echo "*** Starting a second Alice node to trigger a GRANDPA equivocation ***"
+	$NODE --chain local --alice --base-path $BASE/alice-twin --port 30335 --ws-port 9946 \
+		--rpc-port 9935 --validator --bootnodes $BOOTNODE \
+		> $BASE-alice-twin.log 2>&1 &
+fi
+
+echo "*** Logs are in $BASE-*.log, press Ctrl-C to stop ***"
+wait
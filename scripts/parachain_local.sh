@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+# This script is meant to be run on Unix/Linux based systems
+set -e
+
+# Builds the collator and launches the Dvine parachain on a local relay chain with zombienet.
+# Expects `polkadot` (from the release-v0.9.13 branch) and `zombienet` on the PATH.
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+for bin in polkadot zombienet; do
+	if ! command -v $bin > /dev/null; then
+		echo "*** $bin not found on the PATH ***"
+		exit 1
+	fi
+done
+
+echo "*** Building the collator ***"
+cargo build --release --manifest-path parachain/Cargo.toml
+
+echo "*** Launching the relay chain and the parachain ***"
+zombienet spawn --provider native parachain/zombienet.toml